@@ -1,18 +1,150 @@
-use darling::FromVariant;
+use std::collections::HashMap;
+
+use darling::{FromDeriveInput, FromMeta, FromVariant};
 use syn::{parse_macro_input, DeriveInput};
 use proc_macro::TokenStream;
-use quote::{quote, format_ident};
+use quote::{quote, quote_spanned, format_ident};
+use syn::spanned::Spanned;
 use convert_case::{Case, Casing};
 
 #[derive(FromVariant, Default)] 
 #[darling(default, attributes(api_error))]
 struct Opts {
-    code: Option<u16>,
+    code: Option<CodeAttr>,
+    code_fn: Option<syn::Path>,
     status: Option<String>,
     kind: Option<String>,
     msg: Option<String>,
     ignore: bool,
     group: bool,
+    level: Option<String>,
+    log: Option<String>,
+    msg_key: Option<String>,
+    grpc: Option<String>,
+    id: Option<String>,
+    allow_duplicate_kind: bool,
+    msg_from_doc: bool,
+    msg_fn: Option<syn::Path>,
+    details_fn: Option<syn::Path>,
+    /// Set by `variant_opts` when `msg` was taken from the doc comment.
+    #[darling(skip)]
+    msg_is_from_doc: bool,
+}
+
+/// The value of the `code` attribute: a literal status code, or the name of the field holding it.
+enum CodeAttr {
+    Literal(u16),
+    Field(syn::Ident),
+}
+
+impl FromMeta for CodeAttr {
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        match expr {
+            syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Int(lit), .. }) => {
+                Ok(CodeAttr::Literal(lit.base10_parse().map_err(|e| darling::Error::custom(e).with_span(lit))?))
+            }
+            syn::Expr::Path(path) => match path.path.get_ident() {
+                Some(ident) => Ok(CodeAttr::Field(ident.clone())),
+                None => Err(darling::Error::custom("expected a status code or a field name").with_span(path)),
+            },
+            syn::Expr::Group(group) => Self::from_expr(&group.expr),
+            _ => Err(darling::Error::unexpected_expr_type(expr).with_span(expr)),
+        }
+    }
+}
+
+/// Options set on the enum itself, e.g. `#[api_error(require_ids)]`.
+#[derive(FromDeriveInput, Default)]
+#[darling(default, attributes(api_error))]
+struct EnumOpts {
+    require_ids: bool,
+    msg_from_doc: bool,
+    no_display: bool,
+}
+
+/// Parses the options of a variant. With `msg_from_doc` (on the variant or the enum),
+/// the doc comment becomes the `msg` when neither `msg` nor `msg_fn` is given.
+fn variant_opts(variant: &syn::Variant, enum_opts: &EnumOpts) -> darling::Result<Opts> {
+    let mut opts = Opts::from_variant(variant)?;
+    if opts.msg.is_none() && opts.msg_fn.is_none() && (opts.msg_from_doc || enum_opts.msg_from_doc) {
+        opts.msg = doc_summary(&variant.attrs);
+        opts.msg_is_from_doc = opts.msg.is_some();
+    }
+    Ok(opts)
+}
+
+/// The lines of the `///` doc comments, trimmed.
+fn doc_lines(attrs: &[syn::Attribute]) -> Vec<String> {
+    attrs.iter().filter(|attr| attr.path().is_ident("doc")).filter_map(|attr| match &attr.meta {
+        syn::Meta::NameValue(syn::MetaNameValue { value: syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(s), .. }), .. }) => {
+            Some(s.value().trim().to_string())
+        }
+        _ => None,
+    }).collect()
+}
+
+/// The full doc comment, or `None` if there is none.
+fn doc_text(attrs: &[syn::Attribute]) -> Option<String> {
+    let text = doc_lines(attrs).join("\n").trim().to_string();
+    if text.is_empty() { None } else { Some(text) }
+}
+
+/// The first paragraph of the doc comment, on a single line.
+fn doc_summary(attrs: &[syn::Attribute]) -> Option<String> {
+    let lines = doc_lines(attrs);
+    let summary = lines.iter()
+        .skip_while(|line| line.is_empty())
+        .take_while(|line| !line.is_empty())
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .join(" ");
+    if summary.is_empty() { None } else { Some(summary) }
+}
+
+/// Returns true if the field is marked with `#[api_error(<flag>)]`, e.g. `#[api_error(ignore)]`.
+fn field_has_flag(field: &syn::Field, flag: &str) -> bool {
+    field.attrs.iter().filter(|attr| attr.path().is_ident("api_error")).any(|attr| {
+        let mut found = false;
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(flag) {
+                found = true;
+            }
+            Ok(())
+        });
+        found
+    })
+}
+
+/// Returns true if the field is marked with `#[api_error(ignore)]`.
+fn field_is_ignored(field: &syn::Field) -> bool {
+    field_has_flag(field, "ignore")
+}
+
+/// Returns true if the field is marked with `#[api_error(source)]`.
+fn field_is_source(field: &syn::Field) -> bool {
+    field_has_flag(field, "source")
+}
+
+/// Calls the function at `path` with references to the fields of the variant, in declaration order,
+/// as bound by the match arm patterns. The call is spanned to the path so that a signature
+/// mismatch is reported on the attribute.
+fn field_call(path: &syn::Path, fields: &syn::Fields) -> proc_macro2::TokenStream {
+    let span = path.span();
+    let args: Vec<syn::Ident> = match fields {
+        syn::Fields::Named(f) => f.named.iter().map(|field| syn::Ident::new(&field.ident.as_ref().unwrap().to_string(), span)).collect(),
+        syn::Fields::Unnamed(f) => (0..f.unnamed.len()).map(|i| syn::Ident::new(&format!("a{}", i), span)).collect(),
+        syn::Fields::Unit => Vec::new(),
+    };
+    quote_spanned! {span=> #path(#( #args ),*) }
+}
+
+/// Returns true if the type is `serde_json::Value` or `Option<serde_json::Value>`, i.e. a `details` field.
+fn is_details_type(ty: &syn::Type) -> bool {
+    let type_string = quote!(#ty).to_string().replace(" ", "");
+    matches!(
+        type_string.as_str(),
+        "serde_json::Value" | "Option<serde_json::Value>" | "std::option::Option<serde_json::Value>"
+    )
 }
 
 
@@ -26,6 +158,11 @@ struct Opts {
 /// - `code = <u16>`: Specifies a raw HTTP status code (e.g., `code = 404`).
 ///   If both `code` and `status` are provided, `code` takes precedence.
 ///
+/// - `code = <field_name>` or `code_fn = <path>`: Computes the status code at runtime, from a named field
+///   (e.g., `code = status` for `UpstreamFailed { status: u16 }`, any integer type works) or with a function
+///   receiving references to the variant's fields and returning a `u16`. An invalid code falls back to
+///   the code given by `status`, or `500`.
+///
 /// - `status = "<StatusCodeString>"`: Specifies the HTTP status using a predefined string.
 ///   (e.g., `status = "NotFound"`). See below for a list of supported strings.
 ///   If neither `code` nor `status` is provided, defaults to `500` (Internal Server Error).
@@ -41,6 +178,26 @@ struct Opts {
 ///       it will be the variant name or a simple format derived from it.
 ///     - If the user provides `Display` (e.g., via `thiserror`), that implementation is used (`self.to_string()`).
 ///
+/// - `msg_from_doc`: When `msg` is not provided, uses the first paragraph of the variant's doc comment
+///   as `msg`, with the same field interpolation (e.g., `/// User {0} not found`). Unlike `msg`, it only
+///   makes the macro generate `Display` when every variant has a message, so it can be used on enums
+///   deriving `thiserror::Error`; add `no_display` on such an enum if all its variants are documented.
+///   Can also be set on the enum.
+///
+/// - `msg_fn = <path>`: A function computing the message, for messages that need logic (e.g., pluralization).
+///   It receives references to the variant's fields, in declaration order, and returns a `String`
+///   (e.g., `fn quota_message(limit: &u32, used: &u32) -> String`). Cannot be combined with `msg`;
+///   like `msg`, it makes the macro generate `Display`.
+///
+/// - `details_fn = <path>`: A function computing the `details`, receiving the variant's fields like `msg_fn`
+///   and returning an `Option<serde_json::Value>`. It takes precedence over a `serde_json::Value` field.
+///
+/// - `msg_key = "<string>"`: The key of the message in a `MessageCatalog` (e.g., `msg_key = "auth.invalid_password"`),
+///   used by the `Localize` middleware to translate the message according to the `Accept-Language` header.
+///   The variant's fields are passed to the catalog template as named arguments (`{field_name}`, or `{0}`, `{1}`
+///   for tuple variants), formatted with `Display`. Fields marked `#[api_error(ignore)]` and `details` fields
+///   are not passed. The message built from `msg` remains the fallback when the catalog has no translation.
+///
 /// - `ignore = <bool>`: (Default: `false`)
 ///   - If `true`, `msg` is *not* provided, and the macro does *not* generate `Display`,
 ///     the message will be the variant name, and fields will not be automatically formatted into the message.
@@ -49,10 +206,51 @@ struct Opts {
 ///   - Its primary use is to simplify the message to just the variant name when no `msg` is given
 ///     and `Display` is not generated by this macro, overriding default field formatting.
 ///
+/// - `log = "<level>"`: The level at which the error is reported when it becomes a response
+///   (e.g., `log = "warn"`). One of `off`, `debug`, `info`, `warn` or `error`; `off` suppresses
+///   reporting for the variant. Defaults to `error` for 5xx status codes and `info` for 4xx status codes.
+///   `level = "<level>"` is accepted as an alias; only one of the two may be given.
+///
+/// - `grpc = "<CodeName>"`: The gRPC status code used when the error is converted into a
+///   `tonic::Status`, overriding the code mapped from the HTTP status (e.g., `grpc = "FailedPrecondition"`).
+///   Accepts the names of the `tonic::Code` variants.
+///
+/// - `id = "<string>"`: A stable identifier of the error, e.g. `id = "E1042"`, set as the `id`
+///   of the `ApiError`. Unlike `kind`, it does not depend on the variant name. Ids must be unique
+///   within an enum; a duplicate is a compile-time error.
+///
+/// - `allow_duplicate_kind`: Kinds must be unique within an enum, including the ones derived from
+///   variant names (`HTTPError` and `HttpError` are both `http_error`), and a duplicate is a compile-time
+///   error. This flag lets the variant share its kind with another one, e.g. for intentional aliases.
+///
 /// - `group = <bool>`: (Default: `false`)
 ///   - If `true`, the variant is expected to hold a single field that itself implements `AsApiErrorTrait`.
 ///     The `as_api_error()` method of this inner error will be called.
-///     Other attributes like `code`, `status`, `msg`, `kind` on the group variant are ignored.
+///     Other attributes like `code`, `status`, `msg`, `kind`, `log`, `id`, `allow_duplicate_kind` on the group variant are ignored.
+///
+/// ## Enum Attributes
+///
+/// - `require_ids`: Placed on the enum (`#[api_error(require_ids)]`), makes every variant
+///   except `group` variants require an `id`.
+///
+/// - `msg_from_doc`: Placed on the enum, applies `msg_from_doc` to every variant.
+///
+/// - `no_display`: Placed on the enum, prevents the macro from generating `Display`,
+///   e.g. when it is derived with `thiserror` while every variant has a message.
+///
+/// ## Field Attributes
+///
+/// - `#[api_error(ignore)]`: The field is not passed to the message catalog (see `msg_key`).
+///
+/// - `#[api_error(source)]`: A clone of the field becomes the `source` of the `ApiError`, which is logged
+///   but never serialized. The field must implement `Clone` and `std::error::Error + Send + Sync + 'static`;
+///   wrap errors that are not `Clone`, such as `std::io::Error`, in an `Arc`. At most one field per variant.
+///
+/// ## Generated `error_catalog` Function
+///
+/// An associated `fn error_catalog() -> &'static [actix_error::ErrorDescriptor]` is generated for the enum.
+/// It lists the `kind`, status, `id` and full doc comment of every variant except `group` variants.
+/// Statuses computed at runtime are recorded as `CatalogStatus::Dynamic`.
 ///
 /// ## Automatic `details` Field Population
 ///
@@ -60,15 +258,22 @@ struct Opts {
 /// or `Option<serde_json::Value>`, this field's value will automatically populate the
 /// `details` field of the generated `ApiError`.
 ///
+/// ## Generated `log_level` Accessor
+///
+/// An inherent `fn log_level(&self) -> actix_error::Level` is generated for the enum. It returns
+/// the level at which the variant is reported (see `log` above), delegating to the inner error for groups.
+///
 /// ## Conditional `std::fmt::Display` Implementation
 ///
 /// The `std::fmt::Display` trait is implemented for the enum by this macro *if and only if*
-/// at least one variant has an explicit `#[api_error(msg = "...")]` attribute.
+/// at least one variant has an explicit `#[api_error(msg = "...")]` (or `msg_fn`) attribute, or every
+/// variant except `group` variants has a message, including one taken from its doc comment with `msg_from_doc`.
+/// The `no_display` enum attribute disables it in both cases.
 /// - If implemented by the macro:
 ///   - Variants with `msg` will use that formatted message for their `Display` output.
 ///   - Variants without `msg` will display as their variant name (e.g., `MyEnum::VariantName` displays as "VariantName").
 ///
-/// If no variants use `#[api_error(msg = "...")]`, you are expected to provide your own
+/// Otherwise, you are expected to provide your own
 /// `Display` implementation (e.g., using the `thiserror` crate or manually).
 /// The `as_api_error` method will then use `self.to_string()` for the `ApiError` message if `msg` is not set on the variant.
 ///
@@ -169,23 +374,95 @@ pub fn derive(input: TokenStream) -> TokenStream {
     };
     let variants_data = &enum_data.variants;
 
-    // Determine if any variant has an explicit 'msg' attribute.
-    // This will decide if a Display impl should be generated by this macro.
+    let enum_opts = match EnumOpts::from_derive_input(&ast) {
+        Ok(opts) => opts,
+        Err(e) => return TokenStream::from(e.write_errors()),
+    };
+
+    // Determine if a Display impl should be generated by this macro: when any variant has an explicit
+    // `msg` or `msg_fn`, or when every variant gets its message from one of them or from a doc comment.
+    // Doc messages alone do not otherwise count, and `no_display` opts out, so that `msg_from_doc`
+    // works with `thiserror`.
     let mut any_variant_has_explicit_msg = false;
+    let mut every_variant_has_msg = true;
+    let mut any_non_group_variant = false;
     for v in variants_data.iter() {
-        match Opts::from_variant(v) {
+        match variant_opts(v, &enum_opts) {
             Ok(opts) => {
-                if opts.msg.is_some() {
+                if (opts.msg.is_some() && !opts.msg_is_from_doc) || opts.msg_fn.is_some() {
                     any_variant_has_explicit_msg = true;
-                    break;
+                }
+                if !opts.group {
+                    any_non_group_variant = true;
+                    every_variant_has_msg &= opts.msg.is_some() || opts.msg_fn.is_some();
                 }
             }
             Err(e) => return TokenStream::from(e.write_errors()), // Propagate error from Opts parsing
         }
     }
+    let generate_display =
+        !enum_opts.no_display && (any_variant_has_explicit_msg || (any_non_group_variant && every_variant_has_msg));
 
-    // Generate the match arms for the as_api_error method
-    let match_arms_results: Vec<Result<proc_macro2::TokenStream, syn::Error>> = variants_data.iter().map(|v| {
+    // Check that kinds and ids are unique within the enum, and that ids are present when the enum requires them.
+    let mut variants_by_kind: HashMap<String, &syn::Variant> = HashMap::new();
+    let mut variants_by_id: HashMap<String, &syn::Variant> = HashMap::new();
+    let mut check_errors: Option<syn::Error> = None;
+    let mut push_error = |error: syn::Error| match &mut check_errors {
+        Some(errors) => errors.combine(error),
+        None => check_errors = Some(error),
+    };
+    for v in variants_data.iter() {
+        let opts = variant_opts(v, &enum_opts).unwrap_or_default();
+        if opts.group {
+            continue;
+        }
+        if !opts.allow_duplicate_kind {
+            let kind = opts.kind.clone().unwrap_or_else(|| v.ident.to_string().to_case(Case::Snake));
+            match variants_by_kind.get(&kind) {
+                Some(first) => {
+                    let mut error = syn::Error::new_spanned(
+                        &v.ident,
+                        format!("Duplicate kind \"{}\": variant {} has the same kind as {}. Use `allow_duplicate_kind` if this is intended.", kind, v.ident, first.ident),
+                    );
+                    error.combine(syn::Error::new_spanned(&first.ident, format!("Kind \"{}\" is first used here.", kind)));
+                    push_error(error);
+                }
+                None => {
+                    variants_by_kind.insert(kind, v);
+                }
+            }
+        }
+        let error = match opts.id {
+            None if enum_opts.require_ids => Some(syn::Error::new_spanned(
+                &v.ident,
+                format!("Variant {} has no `id`, which is required by `#[api_error(require_ids)]` on {}.", v.ident, ident_name),
+            )),
+            None => None,
+            Some(id) => match variants_by_id.get(&id) {
+                Some(first) => {
+                    let mut error = syn::Error::new_spanned(
+                        &v.ident,
+                        format!("Duplicate id \"{}\": variant {} uses the same id as {}.", id, v.ident, first.ident),
+                    );
+                    error.combine(syn::Error::new_spanned(&first.ident, format!("Id \"{}\" is first used here.", id)));
+                    Some(error)
+                }
+                None => {
+                    variants_by_id.insert(id, v);
+                    None
+                }
+            },
+        };
+        if let Some(error) = error {
+            push_error(error);
+        }
+    }
+    if let Some(errors) = check_errors {
+        return TokenStream::from(errors.to_compile_error());
+    }
+
+    // Generate the match arms for the as_api_error method, along with the catalog descriptors
+    let match_arms_results: Vec<Result<(proc_macro2::TokenStream, Option<proc_macro2::TokenStream>), syn::Error>> = variants_data.iter().map(|v| {
         let variant_ident = &v.ident;
         
         // Determine the pattern for matching fields
@@ -201,12 +478,12 @@ pub fn derive(input: TokenStream) -> TokenStream {
             syn::Fields::Unit => quote! {},
         };
 
-        let opts = match Opts::from_variant(&v) {
+        let opts = match variant_opts(v, &enum_opts) {
             Ok(opts) => opts,
             Err(e) => return Err(e.into()),
         };
             
-        let status_code_val = if let Some(code) = opts.code {
+        let status_code_val = if let Some(CodeAttr::Literal(code)) = opts.code {
             code
         } else if let Some(ref error_kind_str) = opts.status {
             match error_kind_str.as_str() {
@@ -240,18 +517,139 @@ pub fn derive(input: TokenStream) -> TokenStream {
         };
         
         // Validate status code
-        if let Err(e) = actix_web::http::StatusCode::from_u16(status_code_val) {
+        // Same bounds as `http::StatusCode::from_u16`, checked here to keep the proc-macro free of HTTP crates.
+        if !(100..=999).contains(&status_code_val) {
              return Err(syn::Error::new_spanned(
                  &v.ident, 
-                 format!("Invalid status code {} for variant {}: {}", status_code_val, variant_ident, e)
-            )); // Removed .into() as to_compile_error is not needed here
+                 format!("Invalid status code {} for variant {}: status codes must be between 100 and 999", status_code_val, variant_ident)
+            ));
         }
         
+        // A status computed at runtime, with the static status above as the fallback.
+        let dynamic_code = match (&opts.code, &opts.code_fn) {
+            (Some(_), Some(code_fn)) => {
+                return Err(syn::Error::new_spanned(
+                    code_fn,
+                    format!("Variant {} sets both `code` and `code_fn`; use only one of them.", variant_ident),
+                ));
+            }
+            (Some(CodeAttr::Field(field)), None) => {
+                let is_field = matches!(&v.fields, syn::Fields::Named(f) if f.named.iter().any(|named| named.ident.as_ref() == Some(field)));
+                if !is_field {
+                    return Err(syn::Error::new_spanned(
+                        field,
+                        format!("Variant {} has no field named `{}`; `code` must be a status code or the name of a field.", variant_ident, field),
+                    ));
+                }
+                Some(quote_spanned! {field.span()=> ::std::convert::TryFrom::try_from(::std::clone::Clone::clone(#field)).ok() })
+            }
+            (None, Some(code_fn)) => {
+                let call = field_call(code_fn, &v.fields);
+                Some(quote_spanned! {code_fn.span()=> {
+                    let code: u16 = #call;
+                    Some(code)
+                }})
+            }
+            _ => None,
+        };
+        let catalog_status = if dynamic_code.is_some() {
+            quote! { ::actix_error::CatalogStatus::Dynamic }
+        } else {
+            quote! { ::actix_error::CatalogStatus::Fixed(#status_code_val) }
+        };
+        let code_expr = match dynamic_code {
+            Some(dynamic_code) => quote! {{
+                // The field may already be a `u16`.
+                #[allow(clippy::useless_conversion)]
+                let code: Option<u16> = #dynamic_code;
+                // Same bounds as `http::StatusCode::from_u16`.
+                code.filter(|code| (100..=999).contains(code)).unwrap_or(#status_code_val)
+            }},
+            None => quote! { #status_code_val },
+        };
+
+        if opts.log.is_some() && opts.level.is_some() {
+            return Err(syn::Error::new_spanned(
+                v,
+                format!("Variant {} sets both `log` and `level`; they are aliases, use only `log`.", variant_ident),
+            ));
+        }
+        let level_call = match opts.log.as_deref().or(opts.level.as_deref()) {
+            None => quote! {},
+            Some(level) => {
+                let level_variant = match level {
+                    "off" => format_ident!("Off"),
+                    "debug" => format_ident!("Debug"),
+                    "info" => format_ident!("Info"),
+                    "warn" => format_ident!("Warn"),
+                    "error" => format_ident!("Error"),
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            v,
+                            format!("Invalid log level \"{}\" for variant {}. Supported values are: off, debug, info, warn, error.", level, variant_ident),
+                        ));
+                    }
+                };
+                quote! { .with_level(::actix_error::Level::#level_variant) }
+            }
+        };
+
+        let grpc_call = match opts.grpc.as_deref() {
+            None => quote! {},
+            Some(name) => {
+                let grpc_code: i32 = match name {
+                    "Ok" => 0,
+                    "Cancelled" => 1,
+                    "Unknown" => 2,
+                    "InvalidArgument" => 3,
+                    "DeadlineExceeded" => 4,
+                    "NotFound" => 5,
+                    "AlreadyExists" => 6,
+                    "PermissionDenied" => 7,
+                    "ResourceExhausted" => 8,
+                    "FailedPrecondition" => 9,
+                    "Aborted" => 10,
+                    "OutOfRange" => 11,
+                    "Unimplemented" => 12,
+                    "Internal" => 13,
+                    "Unavailable" => 14,
+                    "DataLoss" => 15,
+                    "Unauthenticated" => 16,
+                    _ => {
+                        return Err(syn::Error::new_spanned(
+                            v,
+                            format!("Invalid grpc code \"{}\" for variant {}. Supported values are the names of `tonic::Code` variants, e.g. FailedPrecondition.", name, variant_ident),
+                        ));
+                    }
+                };
+                quote! { .with_grpc_code(#grpc_code) }
+            }
+        };
+
+        let id_call = match opts.id.as_deref() {
+            None => quote! {},
+            Some(id) => quote! { .with_id(#id) },
+        };
+
         let kind_str = opts.kind.unwrap_or_else(|| variant_ident.to_string().to_case(Case::Snake));
 
+        if let (Some(_), Some(msg_fn)) = (&opts.msg, &opts.msg_fn) {
+            return Err(syn::Error::new_spanned(
+                msg_fn,
+                format!("Variant {} sets both `msg` and `msg_fn`; use only one of them.", variant_ident),
+            ));
+        }
+
         // Generate the message expression
-        let message_expr = match opts.msg {
-            Some(ref msg_s) => {
+        let message_expr = match (&opts.msg_fn, &opts.msg) {
+            (Some(msg_fn), _) => {
+                let call = field_call(msg_fn, &v.fields);
+                quote_spanned! {msg_fn.span()=> {
+                    let message: ::std::string::String = #call;
+                    message
+                }}
+            }
+            (None, Some(msg_s)) => {
                 match &v.fields {
                     syn::Fields::Unnamed(f) => {
                         // For unnamed fields, format if msg_s contains placeholders and there are fields.
@@ -268,7 +666,10 @@ pub fn derive(input: TokenStream) -> TokenStream {
                         if opts.ignore || f.named.is_empty() || !msg_s.contains('{') { // Heuristic: check for presence of '{'
                             quote! { #msg_s.to_owned() } // Treat as literal
                         } else {
-                            let named_field_idents = f.named.iter().map(|field| field.ident.as_ref().unwrap());
+                            // The source field can still be referenced, as an implicitly captured argument.
+                            let named_field_idents = f.named.iter()
+                                .filter(|field| !field_is_source(field))
+                                .map(|field| field.ident.as_ref().unwrap());
                             let format_assignments = named_field_idents.map(|ident| quote! { #ident = #ident }).collect::<Vec<_>>();
                             quote! { format!(#msg_s, #( #format_assignments ),*) }
                         }
@@ -279,9 +680,9 @@ pub fn derive(input: TokenStream) -> TokenStream {
                     }
                 }
             }
-            None => {
+            (None, None) => {
                 // If no `msg` attribute is provided in `api_error`:
-                if any_variant_has_explicit_msg {
+                if generate_display {
                     // If the macro is generating a Display impl for this enum (because some other variant has a msg),
                     // we default to the variant's name to avoid recursion with the macro-generated Display.
                     // This matches test expectations for variants like ErrorEn::MissingMessageVariant.
@@ -337,21 +738,81 @@ pub fn derive(input: TokenStream) -> TokenStream {
                     // Unit variants cannot have details fields.
                 }
             }
+
+            // An explicit function takes precedence over the detected field.
+            if let Some(ref details_fn) = opts.details_fn {
+                let call = field_call(details_fn, &v.fields);
+                details_expr = quote_spanned! {details_fn.span()=> {
+                    let details: ::std::option::Option<::actix_error::__private::serde_json::Value> = #call;
+                    details
+                }};
+            }
         }
         
+        // The field marked `#[api_error(source)]`, bound as in the match arm patterns, becomes the source.
+        let source_fields: Vec<(proc_macro2::TokenStream, &syn::Field)> = match &v.fields {
+            syn::Fields::Named(f) => f.named.iter()
+                .filter(|field| field_is_source(field))
+                .map(|field| { let ident = field.ident.as_ref().unwrap(); (quote! { #ident }, field) })
+                .collect(),
+            syn::Fields::Unnamed(f) => f.unnamed.iter().enumerate()
+                .filter(|(_, field)| field_is_source(field))
+                .map(|(i, field)| { let ident = format_ident!("a{}", i); (quote! { #ident }, field) })
+                .collect(),
+            syn::Fields::Unit => Vec::new(),
+        };
+        if let Some((_, field)) = source_fields.get(1) {
+            return Err(syn::Error::new_spanned(
+                field,
+                format!("Variant {} has more than one `#[api_error(source)]` field.", variant_ident),
+            ));
+        }
+        let source_call = match source_fields.first() {
+            Some((binding, field)) if !opts.group => {
+                quote_spanned! {field.ty.span()=> .with_source(::std::clone::Clone::clone(#binding)) }
+            }
+            _ => quote! {},
+        };
+
         // Generate the ApiError construction call
+        let message_key_call = match opts.msg_key {
+            None => quote! {},
+            Some(ref key) => {
+                let args: Vec<proc_macro2::TokenStream> = match &v.fields {
+                    syn::Fields::Named(f) => f.named.iter()
+                        .filter(|field| !field_is_ignored(field) && !field_is_source(field) && !is_details_type(&field.ty))
+                        .map(|field| {
+                            let ident = field.ident.as_ref().unwrap();
+                            let name = ident.to_string();
+                            quote! { (#name.to_string(), #ident.to_string()) }
+                        })
+                        .collect(),
+                    syn::Fields::Unnamed(f) => f.unnamed.iter().enumerate()
+                        .filter(|(_, field)| !field_is_ignored(field) && !field_is_source(field) && !is_details_type(&field.ty))
+                        .map(|(i, _)| {
+                            let ident = format_ident!("a{}", i);
+                            let name = i.to_string();
+                            quote! { (#name.to_string(), #ident.to_string()) }
+                        })
+                        .collect(),
+                    syn::Fields::Unit => Vec::new(),
+                };
+                quote! { .with_message_key(#key, vec![#( #args ),*]) }
+            }
+        };
+
         let api_error_call = if opts.group {
             // Assumes the first field of a tuple variant is 'a0' if 'group' is true
             let group_var = format_ident!("a0"); 
             quote! { #group_var.as_api_error() }
         } else {
-            quote! { ApiError::new(#status_code_val, #kind_str, #message_expr, #details_expr) } 
+            quote! { ApiError::new(#code_expr, #kind_str, #message_expr, #details_expr)#id_call #level_call #message_key_call #grpc_call #source_call }
         };
 
         // If fields are destructured by field_pats but not necessarily used directly in api_error_call
         // (e.g. if message comes from self.to_string() or variant_name),
         // this dummy assignment helps to silence "unused variable" warnings.
-        let dummy_field_usage = match (opts.msg.is_none(), &v.fields) {
+        let dummy_field_usage = match (opts.msg.is_none() && opts.msg_fn.is_none(), &v.fields) {
             (true, syn::Fields::Unnamed(f)) if !f.unnamed.is_empty() && !opts.group => {
                 let idents = f.unnamed.iter().enumerate().map(|(i, _)| format_ident!("a{}", i));
                 quote! { let _ = (#( #idents ),*); }
@@ -363,27 +824,47 @@ pub fn derive(input: TokenStream) -> TokenStream {
             _ => quote! {}, // No dummy usage needed if msg is Some, or it's a unit variant, or a group error
         };
 
-        Ok(quote! {
+        // Group variants are described by the catalog of the wrapped error.
+        let descriptor = if opts.group {
+            None
+        } else {
+            let id = match opts.id.as_deref() {
+                Some(id) => quote! { Some(#id) },
+                None => quote! { None },
+            };
+            let description = match doc_text(&v.attrs) {
+                Some(text) => quote! { Some(#text) },
+                None => quote! { None },
+            };
+            Some(quote! {
+                ::actix_error::ErrorDescriptor { kind: #kind_str, status: #catalog_status, id: #id, description: #description }
+            })
+        };
+
+        Ok((quote! {
             #ident_name::#variant_ident #field_pats => {
                 #dummy_field_usage
                 #api_error_call
             }
-        })
+        }, descriptor))
     }).collect();
 
     // Handle any errors that occurred during match arm generation
     let mut compiled_match_arms = Vec::new();
+    let mut descriptors = Vec::new();
     for result in match_arms_results {
         match result {
-            Ok(ts) => compiled_match_arms.push(ts),
+            Ok((ts, descriptor)) => {
+                compiled_match_arms.push(ts);
+                descriptors.extend(descriptor);
+            }
             Err(e) => return TokenStream::from(e.to_compile_error()),
         }
     }
 
-    // Conditionally generate Display implementation for the enum.
-    // It's generated if any variant has an explicit 'msg' attribute.
+    // Conditionally generate Display implementation for the enum (see `generate_display`).
     // Otherwise, the user is expected to provide Display (e.g., via thiserror).
-    let display_impl_block = if any_variant_has_explicit_msg {
+    let display_impl_block = if generate_display {
         quote! {
             impl std::fmt::Display for #ident_name {
                 fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -398,6 +879,64 @@ pub fn derive(input: TokenStream) -> TokenStream {
         quote! {} // Empty if no variant has an explicit 'msg' attribute.
     };
 
+    // The framework crates are reached through `actix_error::__private`, which re-exports them under
+    // the same features: the user's crate may not depend on them itself, e.g. when another crate of
+    // the workspace enables a feature of `actix-error` and Cargo unifies it.
+    let actix_impl_block = if cfg!(feature = "actix") {
+        quote! {
+            impl ::actix_error::__private::actix_web::ResponseError for #ident_name {
+                fn status_code(&self) -> ::actix_error::__private::actix_web::http::StatusCode {
+                    // Delegate to the status_code method of the ApiError generated from this enum variant.
+                    ::actix_error::__private::actix_web::ResponseError::status_code(&self.as_api_error())
+                }
+
+                fn error_response(&self) -> ::actix_error::__private::actix_web::HttpResponse {
+                    // Delegate to the error_response method of the ApiError generated from this enum variant.
+                    // This will ensure the ApiError struct (with kind, message, details) is serialized.
+                    ::actix_error::__private::actix_web::ResponseError::error_response(&self.as_api_error())
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let axum_impl_block = if cfg!(feature = "axum") {
+        quote! {
+            impl ::actix_error::__private::axum::response::IntoResponse for #ident_name {
+                fn into_response(self) -> ::actix_error::__private::axum::response::Response {
+                    ::actix_error::__private::axum::response::IntoResponse::into_response(self.as_api_error())
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let tonic_impl_block = if cfg!(feature = "tonic") {
+        quote! {
+            impl From<#ident_name> for ::actix_error::__private::tonic::Status {
+                fn from(error: #ident_name) -> Self {
+                    error.as_api_error().into()
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let graphql_impl_block = if cfg!(feature = "async-graphql") {
+        quote! {
+            impl ::actix_error::__private::async_graphql::ErrorExtensions for #ident_name {
+                fn extend(&self) -> ::actix_error::__private::async_graphql::Error {
+                    ::actix_error::__private::async_graphql::ErrorExtensions::extend(&self.as_api_error())
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
     // Generate the final implementations
     let expanded = quote! {
         impl AsApiErrorTrait for #ident_name {
@@ -408,23 +947,31 @@ pub fn derive(input: TokenStream) -> TokenStream {
             }
         }
 
-        #display_impl_block // Include Display impl only if any_variant_has_explicit_msg is true
+        #display_impl_block // Include Display impl only if generate_display is true
 
-        // The user is expected to provide Debug, e.g., via #[derive(Debug)]
-        // No Debug impl generated by this macro.
-    
-        impl actix_web::ResponseError for #ident_name {
-            fn status_code(&self) -> actix_web::http::StatusCode {
-                // Delegate to the status_code method of the ApiError generated from this enum variant.
-                self.as_api_error().status_code()
+        impl #ident_name {
+            /// The level at which this error is reported when it is turned into a response,
+            /// as set by the variant's `log` attribute or derived from its status code.
+            pub fn log_level(&self) -> ::actix_error::Level {
+                self.as_api_error().level()
             }
-        
-            fn error_response(&self) -> actix_web::HttpResponse {
-                // Delegate to the error_response method of the ApiError generated from this enum variant.
-                // This will ensure the ApiError struct (with kind, message, details) is serialized.
-                self.as_api_error().error_response()
+
+            /// Describes the errors of this enum, one entry per variant (except `group` variants).
+            pub fn error_catalog() -> &'static [::actix_error::ErrorDescriptor] {
+                &[#( #descriptors ),*]
             }
         }
+
+        // The user is expected to provide Debug, e.g., via #[derive(Debug)]
+        // No Debug impl generated by this macro.
+
+        #actix_impl_block
+
+        #axum_impl_block
+
+        #tonic_impl_block
+
+        #graphql_impl_block
     };
 
     TokenStream::from(expanded)
@@ -0,0 +1,124 @@
+use std::any::TypeId;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::{Arc, RwLock};
+
+use crate::{ApiError, AsApiErrorTrait, ValidationErrors};
+
+/// Looks for an error of a given type in an `anyhow::Error` and converts it into an `ApiError`.
+type Resolver = fn(&anyhow::Error) -> Option<ApiError>;
+
+/// The types registered with [`AnyApiError::register`], in registration order.
+static REGISTRY: RwLock<Vec<(TypeId, Resolver)>> = RwLock::new(Vec::new());
+
+/// Finds an error of type `T` in the error itself (including its context) or in its source chain.
+fn resolve<T: AsApiErrorTrait + Error + Send + Sync + 'static>(error: &anyhow::Error) -> Option<ApiError> {
+    error
+        .downcast_ref::<T>()
+        .or_else(|| error.chain().skip(1).find_map(|e| e.downcast_ref::<T>()))
+        .map(AsApiErrorTrait::as_api_error)
+}
+
+/// An `anyhow::Error` that responds with the `ApiError` found in its chain.
+///
+/// Handlers returning `anyhow::Result` lose the status code of the errors they propagate.
+/// Returning `Result<T, AnyApiError>` instead keeps `?` working on any error while the response
+/// is built from the first error found in the chain, looking for:
+///
+/// 1. an `ApiError` (or [`ValidationErrors`]),
+/// 2. a type registered with [`AnyApiError::register`], in registration order.
+///
+/// If none is found, the response is a redacted `500` with `kind = "internal_error"`;
+/// the original error is kept as the `ApiError` source, so it is still logged.
+///
+/// ```rust
+/// use actix_error::{AnyApiError, ApiContext, StatusCode};
+///
+/// async fn handler() -> Result<String, AnyApiError> {
+///     let config = std::fs::read_to_string("config.toml").api_context("config_unavailable", StatusCode::SERVICE_UNAVAILABLE)?;
+///     Ok(config)
+/// }
+/// ```
+pub struct AnyApiError(anyhow::Error);
+
+impl AnyApiError {
+    /// Registers a type implementing `AsApiErrorTrait`, so it is found when it appears
+    /// in the chain of an `AnyApiError`. Registering the same type twice has no effect.
+    pub fn register<T: AsApiErrorTrait + Error + Send + Sync + 'static>() {
+        let mut registry = REGISTRY.write().unwrap();
+        if !registry.iter().any(|(id, _)| *id == TypeId::of::<T>()) {
+            registry.push((TypeId::of::<T>(), resolve::<T>));
+        }
+    }
+
+    /// The `ApiError` this error responds with.
+    pub fn api_error(&self) -> ApiError {
+        resolve::<ApiError>(&self.0)
+            .or_else(|| resolve::<ValidationErrors>(&self.0))
+            .or_else(|| REGISTRY.read().unwrap().iter().find_map(|(_, resolver)| resolver(&self.0)))
+            .unwrap_or_else(|| {
+                let mut error = ApiError::new(500, "internal_error", "Internal server error".to_string(), None);
+                error.source = Some(Arc::from(Box::<dyn Error + Send + Sync>::from(format!("{:#}", self.0))));
+                error
+            })
+    }
+
+    /// The wrapped `anyhow::Error`.
+    pub fn into_inner(self) -> anyhow::Error {
+        self.0
+    }
+}
+
+impl<E: Into<anyhow::Error>> From<E> for AnyApiError {
+    fn from(error: E) -> Self {
+        Self(error.into())
+    }
+}
+
+impl Debug for AnyApiError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        Debug::fmt(&self.0, f)
+    }
+}
+
+impl Display for AnyApiError {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+#[cfg(feature = "actix")]
+impl actix_web::ResponseError for AnyApiError {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        self.api_error().status_code()
+    }
+
+    fn error_response(&self) -> actix_web::HttpResponse {
+        self.api_error().error_response()
+    }
+}
+
+#[cfg(feature = "axum")]
+impl axum::response::IntoResponse for AnyApiError {
+    fn into_response(self) -> axum::response::Response {
+        self.api_error().into_response()
+    }
+}
+
+/// Extension trait turning the error of a `Result` into an `ApiError` with a given kind and status.
+pub trait ApiContext<T> {
+    /// Replaces the error with an `ApiError` of the given `kind` and `status`, whose message is
+    /// the reason phrase of the status. The original error becomes its source and is not sent to the client.
+    fn api_context(self, kind: &str, status: http::StatusCode) -> anyhow::Result<T>;
+}
+
+impl<T, E: Into<anyhow::Error>> ApiContext<T> for Result<T, E> {
+    fn api_context(self, kind: &str, status: http::StatusCode) -> anyhow::Result<T> {
+        self.map_err(|source| {
+            let message = status.canonical_reason().unwrap_or("Error");
+            let mut error = ApiError::with_status(status, kind, message.to_string(), None);
+            error.source = Some(Arc::from(Box::<dyn Error + Send + Sync>::from(source.into())));
+            anyhow::Error::new(error)
+        })
+    }
+}
@@ -0,0 +1,14 @@
+use axum::response::{IntoResponse, Json, Response};
+
+use crate::ApiError;
+
+/// Produces the same status, headers and JSON body as `ResponseError::error_response` does with actix-web,
+/// and stores a clone of the error in the response extensions.
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        self.report();
+        let mut response = (self.code, self.headers.clone(), Json(&self)).into_response();
+        response.extensions_mut().insert(self);
+        response
+    }
+}
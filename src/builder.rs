@@ -0,0 +1,229 @@
+use std::error::Error;
+use std::sync::Arc;
+
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+
+use crate::ApiError;
+
+/// The `kind` used for a bare status code: its reason phrase in snake case, e.g. `not_found`
+/// or `too_many_requests`, or `error_<code>` for codes without a reason phrase.
+fn default_kind(status: StatusCode) -> String {
+    match status.canonical_reason() {
+        Some(reason) => reason
+            .split(|c: char| !c.is_ascii_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_ascii_lowercase)
+            .collect::<Vec<_>>()
+            .join("_"),
+        None => format!("error_{}", status.as_u16()),
+    }
+}
+
+fn default_message(status: StatusCode) -> String {
+    status.canonical_reason().unwrap_or("Error").to_string()
+}
+
+/// A builder for [`ApiError`], created with [`ApiError::builder`] or one of the
+/// status shorthands such as [`ApiError::bad_request`].
+///
+/// Unless set, `kind` and `message` are derived from the status: a `409` builds
+/// `kind = "conflict"` and `message = "Conflict"`.
+///
+/// ```rust
+/// use actix_error::{ApiError, StatusCode};
+///
+/// let error: ApiError = ApiError::bad_request()
+///     .kind("invalid_email")
+///     .message("Email is invalid")
+///     .detail("field", "email")
+///     .header("x-error-kind", "invalid_email")
+///     .build();
+/// assert_eq!(error.code, 400);
+///
+/// let error = ApiError::builder().status(StatusCode::TOO_MANY_REQUESTS).header("retry-after", "30").build();
+/// assert_eq!(error.kind, "too_many_requests");
+/// ```
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct ApiErrorBuilder {
+    status: StatusCode,
+    kind: Option<String>,
+    id: Option<String>,
+    message: Option<String>,
+    details: Option<serde_json::Value>,
+    headers: HeaderMap,
+    source: Option<Arc<dyn Error + Send + Sync>>,
+}
+
+impl ApiErrorBuilder {
+    fn new(status: StatusCode) -> Self {
+        Self {
+            status,
+            kind: None,
+            id: None,
+            message: None,
+            details: None,
+            headers: HeaderMap::new(),
+            source: None,
+        }
+    }
+
+    /// Sets the status code.
+    pub fn status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Sets the machine-readable kind.
+    pub fn kind(mut self, kind: impl Into<String>) -> Self {
+        self.kind = Some(kind.into());
+        self
+    }
+
+    /// Sets the stable identifier, see [`ApiError::with_id`].
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Sets the human-readable message.
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Replaces the details.
+    pub fn details(mut self, details: serde_json::Value) -> Self {
+        self.details = Some(details);
+        self
+    }
+
+    /// Adds a field to the details object. Details that are not an object are replaced.
+    pub fn detail(mut self, name: &str, value: impl Into<serde_json::Value>) -> Self {
+        if !matches!(self.details, Some(serde_json::Value::Object(_))) {
+            self.details = Some(serde_json::Value::Object(serde_json::Map::new()));
+        }
+        if let Some(serde_json::Value::Object(details)) = &mut self.details {
+            details.insert(name.to_string(), value.into());
+        }
+        self
+    }
+
+    /// Adds a header to the response, e.g. `Retry-After` or `WWW-Authenticate`.
+    ///
+    /// The header is ignored if the name or the value is not a valid header name or value,
+    /// so that building an error never fails. Use [`ApiErrorBuilder::try_header`] to get the error.
+    pub fn header<K, V>(self, name: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        HeaderValue: TryFrom<V>,
+    {
+        match (HeaderName::try_from(name), HeaderValue::try_from(value)) {
+            (Ok(name), Ok(value)) => self.with_header(name, value),
+            _ => self,
+        }
+    }
+
+    /// Adds a header to the response, or fails if the name or the value is not a valid header name or value.
+    pub fn try_header<K, V>(self, name: K, value: V) -> Result<Self, http::Error>
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<http::Error>,
+    {
+        let name = HeaderName::try_from(name).map_err(Into::into)?;
+        let value = HeaderValue::try_from(value).map_err(Into::into)?;
+        Ok(self.with_header(name, value))
+    }
+
+    fn with_header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.headers.append(name, value);
+        self
+    }
+
+    /// Attaches the underlying error, see [`ApiError::with_source`].
+    pub fn source<E: Error + Send + Sync + 'static>(mut self, source: E) -> Self {
+        self.source = Some(Arc::new(source));
+        self
+    }
+
+    /// Builds the error.
+    pub fn build(self) -> ApiError {
+        let kind = self.kind.unwrap_or_else(|| default_kind(self.status));
+        let message = self.message.unwrap_or_else(|| default_message(self.status));
+        let mut error = ApiError::with_status(self.status, &kind, message, self.details);
+        error.id = self.id;
+        error.headers = self.headers;
+        error.source = self.source;
+        error
+    }
+}
+
+impl From<ApiErrorBuilder> for ApiError {
+    fn from(builder: ApiErrorBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// Builds an error with the status, its reason phrase as message and a kind derived from it.
+impl From<StatusCode> for ApiError {
+    fn from(status: StatusCode) -> Self {
+        ApiErrorBuilder::new(status).build()
+    }
+}
+
+impl ApiError {
+    /// Starts building an error, with status `500` unless set.
+    pub fn builder() -> ApiErrorBuilder {
+        ApiErrorBuilder::new(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    /// Starts building a `400 Bad Request` error.
+    pub fn bad_request() -> ApiErrorBuilder {
+        ApiErrorBuilder::new(StatusCode::BAD_REQUEST)
+    }
+
+    /// Starts building a `401 Unauthorized` error.
+    pub fn unauthorized() -> ApiErrorBuilder {
+        ApiErrorBuilder::new(StatusCode::UNAUTHORIZED)
+    }
+
+    /// Starts building a `403 Forbidden` error.
+    pub fn forbidden() -> ApiErrorBuilder {
+        ApiErrorBuilder::new(StatusCode::FORBIDDEN)
+    }
+
+    /// Starts building a `404 Not Found` error about a resource, e.g. `not_found("user")`
+    /// has the message `"User not found"`.
+    pub fn not_found(resource: &str) -> ApiErrorBuilder {
+        let mut chars = resource.chars();
+        let resource: String = chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default();
+        ApiErrorBuilder::new(StatusCode::NOT_FOUND).message(format!("{} not found", resource))
+    }
+
+    /// Starts building a `409 Conflict` error.
+    pub fn conflict() -> ApiErrorBuilder {
+        ApiErrorBuilder::new(StatusCode::CONFLICT)
+    }
+
+    /// Starts building a `422 Unprocessable Entity` error.
+    pub fn unprocessable_entity() -> ApiErrorBuilder {
+        ApiErrorBuilder::new(StatusCode::UNPROCESSABLE_ENTITY)
+    }
+
+    /// Starts building a `429 Too Many Requests` error.
+    pub fn too_many_requests() -> ApiErrorBuilder {
+        ApiErrorBuilder::new(StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    /// Starts building a `500 Internal Server Error` error.
+    pub fn internal_server_error() -> ApiErrorBuilder {
+        ApiErrorBuilder::new(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    /// Starts building a `503 Service Unavailable` error.
+    pub fn service_unavailable() -> ApiErrorBuilder {
+        ApiErrorBuilder::new(StatusCode::SERVICE_UNAVAILABLE)
+    }
+}
@@ -0,0 +1,58 @@
+use serde::{Serialize, Serializer};
+
+/// Describes an error that an API can return, as generated by the derive macro.
+///
+/// Each derived enum has an `error_catalog()` function returning one descriptor per variant,
+/// which can be serialized to publish the list of errors, e.g. in the API documentation.
+///
+/// ```rust
+/// use actix_error::{ApiError, AsApiError, AsApiErrorTrait, CatalogStatus, ErrorDescriptor};
+///
+/// #[derive(AsApiError, Debug)]
+/// pub enum AuthError {
+///     /// Invalid password
+///     ///
+///     /// The password does not match the one of the account.
+///     #[api_error(status = "Unauthorized", id = "E1001", msg = "Invalid password")]
+///     InvalidPassword,
+/// }
+///
+/// assert_eq!(
+///     AuthError::error_catalog(),
+///     &[ErrorDescriptor {
+///         kind: "invalid_password",
+///         status: CatalogStatus::Fixed(401),
+///         id: Some("E1001"),
+///         description: Some("Invalid password\n\nThe password does not match the one of the account."),
+///     }]
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ErrorDescriptor {
+    /// The `kind` of the error.
+    pub kind: &'static str,
+    /// The HTTP status code of the error.
+    pub status: CatalogStatus,
+    /// The stable identifier of the error, if any.
+    pub id: Option<&'static str>,
+    /// The doc comment of the variant, if any.
+    pub description: Option<&'static str>,
+}
+
+/// The status of an [`ErrorDescriptor`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CatalogStatus {
+    /// A status code known at compile time, serialized as a number.
+    Fixed(u16),
+    /// A status code computed at runtime (`code = field` or `code_fn`), serialized as `"dynamic"`.
+    Dynamic,
+}
+
+impl Serialize for CatalogStatus {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            CatalogStatus::Fixed(code) => serializer.serialize_u16(*code),
+            CatalogStatus::Dynamic => serializer.serialize_str("dynamic"),
+        }
+    }
+}
@@ -0,0 +1,234 @@
+use std::fmt::{Display, Formatter};
+
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+use http::StatusCode;
+
+use crate::{ApiError, AsApiErrorTrait};
+
+/// How [`ApiErrors`] chooses the status code of the response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum StatusPolicy {
+    /// The status of the first error.
+    #[default]
+    First,
+    /// The highest status among the errors (e.g. `500` wins over `404`).
+    Highest,
+    /// The status shared by the most errors, the earliest one winning ties.
+    MostCommon,
+    /// A fixed status, e.g. `207 Multi-Status` or `400 Bad Request`.
+    Fixed(StatusCode),
+}
+
+/// The body layout used by [`ApiErrors`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ErrorsFormat {
+    /// `{"errors": [{"kind": ..., "message": ..., "details": ...}]}`, each error serialized like `ApiError`.
+    #[default]
+    Plain,
+    /// JSON:API error objects: `{"errors": [{"status": "404", "code": kind, "title": reason, "detail": message, "meta": details}]}`.
+    JsonApi,
+}
+
+/// Several `ApiError`s returned in a single response, e.g. one per failed item of a batch.
+///
+/// The status code is chosen by a [`StatusPolicy`] (the first error's by default), and the body
+/// lists every error under `errors`. An empty collection responds with `500`, unless the policy is
+/// [`StatusPolicy::Fixed`].
+///
+/// With actix-web, the response extensions hold the `ApiErrors` rather than an `ApiError`; read them
+/// with [`ApiErrors::from_response_ext`]. The middleware handle each error of the collection: `ErrorMetrics`
+/// counts them, `Localize` translates their messages and `ErrorRenderer` renders them with the
+/// [`ErrorConfig`], the envelope wrapping the whole body. JSON:API bodies keep their layout.
+///
+/// ```rust
+/// use actix_error::{ApiError, ApiErrors, StatusCode, StatusPolicy};
+///
+/// let errors: ApiErrors = vec![
+///     ApiError::new(404, "item_not_found", "Item 3 not found".to_string(), None),
+///     ApiError::new(409, "item_locked", "Item 7 is locked".to_string(), None),
+/// ]
+/// .into_iter()
+/// .collect();
+/// let errors = errors.with_policy(StatusPolicy::Fixed(StatusCode::MULTI_STATUS));
+/// assert_eq!(errors.status(), 207);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ApiErrors {
+    errors: Vec<ApiError>,
+    policy: StatusPolicy,
+    format: ErrorsFormat,
+}
+
+impl ApiErrors {
+    /// Creates an empty collection.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an error to the collection.
+    pub fn push(&mut self, error: &impl AsApiErrorTrait) -> &mut Self {
+        self.errors.push(error.as_api_error());
+        self
+    }
+
+    /// Sets the policy choosing the status code of the response.
+    pub fn with_policy(mut self, policy: StatusPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Sets the body layout.
+    pub fn with_format(mut self, format: ErrorsFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// The errors, in insertion order.
+    pub fn errors(&self) -> &[ApiError] {
+        &self.errors
+    }
+
+    /// Returns true if the collection holds no error.
+    pub fn is_empty(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// The number of errors.
+    pub fn len(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// The status code of the response, as chosen by the policy.
+    pub fn status(&self) -> StatusCode {
+        let codes = self.errors.iter().map(|e| e.code);
+        let chosen = match self.policy {
+            StatusPolicy::Fixed(code) => return code,
+            StatusPolicy::First => codes.into_iter().next(),
+            StatusPolicy::Highest => codes.max(),
+            StatusPolicy::MostCommon => {
+                let mut counts: Vec<(StatusCode, usize)> = Vec::new();
+                for code in codes {
+                    match counts.iter_mut().find(|(c, _)| *c == code) {
+                        Some((_, count)) => *count += 1,
+                        None => counts.push((code, 1)),
+                    }
+                }
+                // `max_by_key` returns the last maximum, so iterate in reverse to favor the earliest.
+                counts.into_iter().rev().max_by_key(|(_, count)| *count).map(|(code, _)| code)
+            }
+        };
+        chosen.unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    /// The errors, mutably, e.g. to translate their messages.
+    #[cfg(feature = "actix")]
+    pub(crate) fn errors_mut(&mut self) -> &mut [ApiError] {
+        &mut self.errors
+    }
+
+    /// Serializes the errors with `config`, for a request with the given method and path: each error
+    /// gets the configured fields and naming, and the body is nested in the envelope. JSON:API bodies are
+    /// serialized as usual.
+    #[cfg(feature = "actix")]
+    pub(crate) fn render(&self, config: &crate::ErrorConfig, method: &str, path: &str) -> serde_json::Value {
+        match self.format {
+            ErrorsFormat::Plain => {
+                let errors: Vec<serde_json::Value> =
+                    self.errors.iter().map(|error| config.render_fields(error, method, path)).collect();
+                config.wrap(serde_json::json!({ "errors": errors }))
+            }
+            ErrorsFormat::JsonApi => serde_json::to_value(self).unwrap_or_default(),
+        }
+    }
+
+    /// Retrieves the `ApiErrors` that produced a response, like [`ApiError::from_response_ext`].
+    /// Returns `None` if the response was not produced by an `ApiErrors`.
+    #[cfg(feature = "actix")]
+    pub fn from_response_ext<B>(res: &actix_web::dev::ServiceResponse<B>) -> Option<ApiErrors> {
+        res.response().extensions().get::<ApiErrors>().cloned()
+    }
+}
+
+impl<T: AsApiErrorTrait> FromIterator<T> for ApiErrors {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut errors = Self::new();
+        errors.extend(iter);
+        errors
+    }
+}
+
+impl<T: AsApiErrorTrait> Extend<T> for ApiErrors {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.errors.extend(iter.into_iter().map(|e| e.as_api_error()));
+    }
+}
+
+/// An `ApiError` serialized as a JSON:API error object.
+struct JsonApiError<'a>(&'a ApiError);
+
+impl Serialize for JsonApiError<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let error = self.0;
+        let title = error.code.canonical_reason();
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("status", error.code.as_str())?;
+        map.serialize_entry("code", &error.kind)?;
+        if let Some(title) = title {
+            map.serialize_entry("title", title)?;
+        }
+        map.serialize_entry("detail", &error.message)?;
+        if let Some(details) = &error.details {
+            map.serialize_entry("meta", details)?;
+        }
+        map.end()
+    }
+}
+
+impl Serialize for ApiErrors {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(1))?;
+        match self.format {
+            ErrorsFormat::Plain => map.serialize_entry("errors", &self.errors)?,
+            ErrorsFormat::JsonApi => {
+                let errors: Vec<JsonApiError> = self.errors.iter().map(JsonApiError).collect();
+                map.serialize_entry("errors", &errors)?
+            }
+        }
+        map.end()
+    }
+}
+
+impl Display for ApiErrors {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "{} errors", self.errors.len())?;
+        for error in &self.errors {
+            write!(f, "; {}", error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ApiErrors {}
+
+#[cfg(feature = "actix")]
+impl actix_web::ResponseError for ApiErrors {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        actix_web::http::StatusCode::from_u16(self.status().as_u16()).expect("status codes are validated by ApiError")
+    }
+
+    fn error_response(&self) -> actix_web::HttpResponse {
+        for error in &self.errors {
+            error.report();
+        }
+        let content_type = match self.format {
+            ErrorsFormat::Plain => "application/json",
+            ErrorsFormat::JsonApi => "application/vnd.api+json",
+        };
+        let mut response = actix_web::HttpResponse::build(self.status_code())
+            .content_type(content_type)
+            .json(self);
+        response.extensions_mut().insert(self.clone());
+        response
+    }
+}
@@ -0,0 +1,255 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use convert_case::{Case, Casing};
+
+use crate::ApiError;
+
+/// The case applied to the field names of the rendered error and of its envelope by [`ErrorConfig::naming`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Naming {
+    /// Leaves names as they are.
+    #[default]
+    Unchanged,
+    /// `user_not_found` becomes `userNotFound`.
+    CamelCase,
+    /// `userNotFound` becomes `user_not_found`.
+    SnakeCase,
+}
+
+impl Naming {
+    /// Converts a name to this case, with the same rules as the derive macro uses for the default
+    /// `kind` (e.g. `HTTPError` becomes `http_error`). Leading underscores are kept.
+    pub fn apply(self, name: &str) -> String {
+        let case = match self {
+            Naming::Unchanged => return name.to_string(),
+            Naming::CamelCase => Case::Camel,
+            Naming::SnakeCase => Case::Snake,
+        };
+        let rest = name.trim_start_matches('_');
+        format!("{}{}", &name[..name.len() - rest.len()], rest.to_case(case))
+    }
+
+    /// Converts the keys of the objects in `value`, recursively.
+    fn apply_to_keys(self, value: serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::Object(map) => {
+                map.into_iter().map(|(key, value)| (self.apply(&key), self.apply_to_keys(value))).collect()
+            }
+            serde_json::Value::Array(items) => items.into_iter().map(|item| self.apply_to_keys(item)).collect(),
+            value => value,
+        }
+    }
+}
+
+/// How an `ApiError` is serialized in responses: the fields added next to `kind`, `message`
+/// and `details`, the envelope wrapping the error and the case of names.
+///
+/// With actix-web, register it in the app data and wrap the app with [`ErrorRenderer`](crate::ErrorRenderer),
+/// which re-renders every `ApiError` response with the configuration of the route (so scopes may
+/// register their own). All fields are disabled by default:
+///
+/// | Method        | Field       | Example                    |
+/// |---------------|-------------|----------------------------|
+/// | `status`      | `status`    | `404`                      |
+/// | `timestamp`   | `timestamp` | `"2024-05-01T12:00:00Z"`   |
+/// | `path`        | `path`      | `"/users/42"`              |
+/// | `method`      | `method`    | `"GET"`                    |
+/// | `instance_id` | `instance`  | `"api-eu-1"`               |
+///
+/// With [`ErrorConfig::envelope`], the error is nested under a key, e.g. `{"error": {...}}`,
+/// next to the fields added with [`ErrorConfig::envelope_field`], e.g. `"success": false`.
+///
+/// ```rust
+/// use actix_error::{ApiError, ErrorConfig};
+///
+/// let config = ErrorConfig::new().status(true).path(true).instance_id("api-eu-1");
+/// let error = ApiError::new(404, "user_not_found", "User not found".to_string(), None);
+/// assert_eq!(
+///     config.render(&error, "GET", "/users/42"),
+///     serde_json::json!({
+///         "kind": "user_not_found",
+///         "message": "User not found",
+///         "status": 404,
+///         "path": "/users/42",
+///         "instance": "api-eu-1"
+///     })
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorConfig {
+    status: bool,
+    timestamp: bool,
+    path: bool,
+    method: bool,
+    instance_id: Option<String>,
+    envelope: Option<String>,
+    envelope_fields: serde_json::Map<String, serde_json::Value>,
+    naming: Naming,
+    naming_details: bool,
+    constraint_names: bool,
+}
+
+impl ErrorConfig {
+    /// Creates a configuration adding no field.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the numeric status code as `status`.
+    pub fn status(mut self, enabled: bool) -> Self {
+        self.status = enabled;
+        self
+    }
+
+    /// Adds the time at which the error was rendered, in RFC 3339 format (UTC), as `timestamp`.
+    pub fn timestamp(mut self, enabled: bool) -> Self {
+        self.timestamp = enabled;
+        self
+    }
+
+    /// Adds the request path as `path`.
+    pub fn path(mut self, enabled: bool) -> Self {
+        self.path = enabled;
+        self
+    }
+
+    /// Adds the request method as `method`.
+    pub fn method(mut self, enabled: bool) -> Self {
+        self.method = enabled;
+        self
+    }
+
+    /// Adds an identifier of the server instance, e.g. a host or pod name, as `instance`.
+    pub fn instance_id(mut self, instance_id: impl Into<String>) -> Self {
+        self.instance_id = Some(instance_id.into());
+        self
+    }
+
+    /// Nests the error under `key` in the body, e.g. `{"error": {"kind": ..., "message": ...}}`.
+    pub fn envelope(mut self, key: impl Into<String>) -> Self {
+        self.envelope = Some(key.into());
+        self
+    }
+
+    /// Adds a field next to the error in the envelope, e.g. `envelope_field("success", false)`.
+    /// Only used when an [`envelope`](ErrorConfig::envelope) key is set.
+    pub fn envelope_field(mut self, name: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.envelope_fields.insert(name.to_string(), value.into());
+        self
+    }
+
+    /// Sets the case of the field names: `kind`, `message`, `details` and the added fields, as well as
+    /// the envelope key and the names of the envelope fields (e.g. `request_id` becomes `requestId`).
+    /// Values, such as the `kind` itself or the contents of the `details`, are left as they are.
+    pub fn naming(mut self, naming: Naming) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    /// Also applies the [`naming`](ErrorConfig::naming) case to the keys of the `details`, recursively.
+    /// Disabled by default, as the details often mirror data whose keys must be kept.
+    pub fn naming_details(mut self, enabled: bool) -> Self {
+        self.naming_details = enabled;
+        self
+    }
+
+    /// Adds the name of the violated database constraint to the details, as `constraint`, for the
+    /// errors converted from database errors (the `sqlx` and `diesel` features). Constraint names
+    /// reveal the database schema, so they are left out by default.
+    pub fn constraint_names(mut self, enabled: bool) -> Self {
+        self.constraint_names = enabled;
+        self
+    }
+
+    /// Returns true if rendering an error with this configuration adds nothing to its serialization.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Serializes the error as configured (fields, naming and envelope), for a request with the given method and path.
+    pub fn render(&self, error: &ApiError, method: &str, path: &str) -> serde_json::Value {
+        self.wrap(self.render_fields(error, method, path))
+    }
+
+    /// Serializes the error with the configured fields and naming, without the envelope.
+    pub(crate) fn render_fields(&self, error: &ApiError, method: &str, path: &str) -> serde_json::Value {
+        let mut value = serde_json::to_value(error).unwrap_or_default();
+        if let serde_json::Value::Object(fields) = &mut value {
+            if let (true, Some(constraint)) = (self.constraint_names, &error.constraint) {
+                let details = fields.entry("details").or_insert_with(|| serde_json::Value::Object(Default::default()));
+                if let serde_json::Value::Object(details) = details {
+                    details.insert("constraint".to_string(), constraint.as_str().into());
+                }
+            }
+            if self.naming_details {
+                if let Some(details) = fields.remove("details") {
+                    fields.insert("details".to_string(), self.naming.apply_to_keys(details));
+                }
+            }
+            if self.status {
+                fields.insert("status".to_string(), error.code.as_u16().into());
+            }
+            if self.timestamp {
+                fields.insert("timestamp".to_string(), rfc3339(SystemTime::now()).into());
+            }
+            if self.path {
+                fields.insert("path".to_string(), path.into());
+            }
+            if self.method {
+                fields.insert("method".to_string(), method.into());
+            }
+            if let Some(instance_id) = &self.instance_id {
+                fields.insert("instance".to_string(), instance_id.as_str().into());
+            }
+            *fields = self.rename_keys(std::mem::take(fields));
+        }
+        value
+    }
+
+    /// Nests `value` in the configured envelope, if any.
+    pub(crate) fn wrap(&self, value: serde_json::Value) -> serde_json::Value {
+        match &self.envelope {
+            Some(key) => {
+                let mut envelope = self.rename_keys(self.envelope_fields.clone());
+                envelope.insert(self.naming.apply(key), value);
+                serde_json::Value::Object(envelope)
+            }
+            None => value,
+        }
+    }
+
+    /// Converts the keys of `fields` (but not of the nested objects) to the configured case.
+    fn rename_keys(&self, fields: serde_json::Map<String, serde_json::Value>) -> serde_json::Map<String, serde_json::Value> {
+        if self.naming == Naming::Unchanged {
+            return fields;
+        }
+        fields.into_iter().map(|(key, value)| (self.naming.apply(&key), value)).collect()
+    }
+}
+
+/// Formats a time as an RFC 3339 UTC timestamp with second precision, e.g. `2024-05-01T12:00:00Z`.
+fn rfc3339(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or_default();
+    let (days, secs_of_day) = (secs / 86_400, secs % 86_400);
+
+    // Converts days since 1970-01-01 to a civil date (proleptic Gregorian calendar).
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        secs_of_day % 3_600 / 60,
+        secs_of_day % 60
+    )
+}
@@ -0,0 +1,98 @@
+use crate::{ApiError, AsApiErrorTrait};
+
+/// Maps the error kind to a status: `NotFound` to `404`, `PermissionDenied` to `403`,
+/// `AlreadyExists` to `409`, `InvalidInput` to `400`, `TimedOut` to `504`, lost connections
+/// to `503` and anything else to `500`. The OS message is not exposed.
+impl AsApiErrorTrait for std::io::Error {
+    fn as_api_error(&self) -> ApiError {
+        use std::io::ErrorKind;
+
+        let (code, kind, message) = match self.kind() {
+            ErrorKind::NotFound => (404, "not_found", "Resource not found"),
+            ErrorKind::PermissionDenied => (403, "permission_denied", "Permission denied"),
+            ErrorKind::AlreadyExists => (409, "already_exists", "Resource already exists"),
+            ErrorKind::InvalidInput => (400, "invalid_input", "Invalid input"),
+            ErrorKind::TimedOut => (504, "timeout", "Operation timed out"),
+            ErrorKind::ConnectionRefused
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::NotConnected => (503, "service_unavailable", "Service unavailable"),
+            _ => (500, "io_error", "Internal server error"),
+        };
+        ApiError::new(code, kind, message.to_string(), None)
+    }
+}
+
+/// Malformed JSON is a `400` with `kind = "invalid_json"`, JSON of the wrong shape a `422`
+/// with `kind = "invalid_json_data"`; both have a fixed message and the position of the problem
+/// in the details, e.g. `{"line": 1, "column": 5}`. The serde message, which may quote the input
+/// or name Rust types, is only kept as the source. I/O errors are a `500`.
+impl AsApiErrorTrait for serde_json::Error {
+    fn as_api_error(&self) -> ApiError {
+        use serde_json::error::Category;
+
+        // The line is 0 when the error has no position, e.g. when deserializing a `Value`.
+        let position = (self.line() > 0).then(|| serde_json::json!({ "line": self.line(), "column": self.column() }));
+        let error = match self.classify() {
+            Category::Syntax | Category::Eof => ApiError::new(400, "invalid_json", "Invalid JSON".to_string(), position),
+            Category::Data => ApiError::new(422, "invalid_json_data", "Invalid JSON data".to_string(), position),
+            Category::Io => ApiError::new(500, "io_error", "Internal server error".to_string(), None),
+        };
+        // `serde_json::Error` is not `Clone`, so the source keeps its message only.
+        error.with_source(std::io::Error::other(self.to_string()))
+    }
+}
+
+impl AsApiErrorTrait for std::num::ParseIntError {
+    fn as_api_error(&self) -> ApiError {
+        ApiError::new(400, "invalid_integer", format!("Invalid integer: {}", self), None)
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl AsApiErrorTrait for uuid::Error {
+    fn as_api_error(&self) -> ApiError {
+        ApiError::new(400, "invalid_uuid", format!("Invalid UUID: {}", self), None)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl AsApiErrorTrait for chrono::ParseError {
+    fn as_api_error(&self) -> ApiError {
+        ApiError::new(400, "invalid_datetime", format!("Invalid date or time: {}", self), None)
+    }
+}
+
+/// Uses the same statuses as actix-web: `413` when the payload is too large, `400` otherwise.
+#[cfg(feature = "actix")]
+impl AsApiErrorTrait for actix_web::error::PayloadError {
+    fn as_api_error(&self) -> ApiError {
+        match self {
+            actix_web::error::PayloadError::Overflow => {
+                ApiError::new(413, "payload_too_large", "Payload too large".to_string(), None)
+            }
+            _ => ApiError::new(400, "invalid_payload", "Invalid payload".to_string(), None),
+        }
+    }
+}
+
+/// Uses the same statuses as actix-web, except for deserialization errors which are
+/// converted like a `serde_json::Error` (`400` or `422`).
+#[cfg(feature = "actix")]
+impl AsApiErrorTrait for actix_web::error::JsonPayloadError {
+    fn as_api_error(&self) -> ApiError {
+        use actix_web::error::JsonPayloadError;
+
+        match self {
+            JsonPayloadError::OverflowKnownLength { .. } | JsonPayloadError::Overflow { .. } => {
+                ApiError::new(413, "payload_too_large", "Payload too large".to_string(), None)
+            }
+            JsonPayloadError::ContentType => {
+                ApiError::new(415, "unsupported_media_type", "Expected a JSON payload".to_string(), None)
+            }
+            JsonPayloadError::Deserialize(error) => error.as_api_error(),
+            JsonPayloadError::Payload(error) => error.as_api_error(),
+            _ => ApiError::new(500, "internal_error", "Internal server error".to_string(), None),
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use crate::{ApiError, AsApiErrorTrait};
+
+/// Records the violated constraint, if reported. It is not sent to clients unless
+/// [`ErrorConfig::constraint_names`](crate::ErrorConfig::constraint_names) is enabled.
+fn with_constraint(mut error: ApiError, constraint: Option<&str>) -> ApiError {
+    error.constraint = constraint.map(str::to_string);
+    error
+}
+
+fn unique_violation(constraint: Option<&str>) -> ApiError {
+    with_constraint(ApiError::new(409, "unique_violation", "Resource already exists".to_string(), None), constraint)
+}
+
+fn foreign_key_violation(constraint: Option<&str>) -> ApiError {
+    with_constraint(ApiError::new(422, "foreign_key_violation", "Referenced resource does not exist".to_string(), None), constraint)
+}
+
+fn not_found() -> ApiError {
+    ApiError::new(404, "not_found", "Resource not found".to_string(), None)
+}
+
+fn unavailable() -> ApiError {
+    ApiError::new(503, "database_unavailable", "Database unavailable".to_string(), None)
+}
+
+/// Any other database error. The message of the database is not exposed.
+fn internal() -> ApiError {
+    ApiError::new(500, "database_error", "Internal server error".to_string(), None)
+}
+
+/// Maps unique violations to `409`, foreign-key violations to `422`, `RowNotFound` to `404`,
+/// pool timeouts to `503` and anything else to `500`.
+#[cfg(feature = "sqlx")]
+impl AsApiErrorTrait for sqlx::Error {
+    fn as_api_error(&self) -> ApiError {
+        match self {
+            sqlx::Error::RowNotFound => not_found(),
+            sqlx::Error::PoolTimedOut | sqlx::Error::PoolClosed => unavailable(),
+            sqlx::Error::Database(error) => match error.kind() {
+                sqlx::error::ErrorKind::UniqueViolation => unique_violation(error.constraint()),
+                sqlx::error::ErrorKind::ForeignKeyViolation => foreign_key_violation(error.constraint()),
+                _ => internal(),
+            },
+            _ => internal(),
+        }
+    }
+}
+
+#[cfg(feature = "sqlx")]
+impl From<sqlx::Error> for ApiError {
+    fn from(error: sqlx::Error) -> Self {
+        error.as_api_error().with_source(error)
+    }
+}
+
+/// Maps unique violations to `409`, foreign-key violations to `422`, `NotFound` to `404`
+/// and anything else to `500`.
+#[cfg(feature = "diesel")]
+impl AsApiErrorTrait for diesel::result::Error {
+    fn as_api_error(&self) -> ApiError {
+        use diesel::result::DatabaseErrorKind;
+
+        match self {
+            diesel::result::Error::NotFound => not_found(),
+            diesel::result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, info) => {
+                unique_violation(info.constraint_name())
+            }
+            diesel::result::Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, info) => {
+                foreign_key_violation(info.constraint_name())
+            }
+            _ => internal(),
+        }
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::result::Error> for ApiError {
+    fn from(error: diesel::result::Error) -> Self {
+        error.as_api_error().with_source(error)
+    }
+}
+
+/// r2d2 only fails when no connection could be obtained in time, which maps to `503`.
+#[cfg(feature = "diesel")]
+impl AsApiErrorTrait for diesel::r2d2::PoolError {
+    fn as_api_error(&self) -> ApiError {
+        unavailable()
+    }
+}
+
+#[cfg(feature = "diesel")]
+impl From<diesel::r2d2::PoolError> for ApiError {
+    fn from(error: diesel::r2d2::PoolError) -> Self {
+        error.as_api_error().with_source(error)
+    }
+}
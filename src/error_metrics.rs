@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+use std::future::{ready, Ready};
+use std::sync::{Arc, Mutex};
+
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use futures_util::future::LocalBoxFuture;
+use metrics::Label;
+
+use crate::{ApiError, ApiErrors};
+
+/// Label value used for the route of a request that did not match any resource,
+/// or whose route is unknown because the error did not reach a response.
+const UNMATCHED_ROUTE: &str = "unmatched";
+/// Label value replacing `kind` and `route` once the cardinality limit is reached.
+const OVERFLOW_LABEL: &str = "other";
+
+/// Middleware counting `ApiError` responses through the `metrics` facade.
+///
+/// Every response produced by an `ApiError` (or a derived error enum) increments a counter,
+/// `api_errors_total` by default, labeled with the error `kind`, the `status` code and the
+/// `route` pattern (e.g. `/users/{id}`). An [`ApiErrors`] response increments it once per error.
+/// Exporting the counter is left to the installed recorder.
+///
+/// To keep the number of series bounded, at most `max_series` distinct label sets are recorded
+/// (1000 by default). Past that limit, errors with a new label set are counted with
+/// `kind` and `route` set to `"other"`. Clones share the same set, so create the middleware once
+/// outside of the `HttpServer` factory to apply the limit across workers.
+///
+/// ```rust
+/// use actix_web::App;
+/// use actix_error::ErrorMetrics;
+///
+/// let app = App::new().wrap(ErrorMetrics::new().max_series(200));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ErrorMetrics {
+    name: String,
+    max_series: usize,
+    seen: Arc<Mutex<HashSet<(String, u16, String)>>>,
+}
+
+impl ErrorMetrics {
+    /// Creates the middleware with the `api_errors_total` counter and a limit of 1000 label sets.
+    pub fn new() -> Self {
+        Self {
+            name: "api_errors_total".to_string(),
+            max_series: 1000,
+            seen: Arc::default(),
+        }
+    }
+
+    /// Sets the name of the counter.
+    pub fn counter_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Sets the maximum number of distinct label sets recorded before collapsing into `"other"`.
+    pub fn max_series(mut self, max_series: usize) -> Self {
+        self.max_series = max_series;
+        self
+    }
+
+    fn record(&self, error: &ApiError, route: &str) {
+        let key = (error.kind.clone(), error.code.as_u16(), route.to_string());
+        let (kind, route) = {
+            let mut seen = self.seen.lock().unwrap();
+            if seen.contains(&key) || seen.len() < self.max_series {
+                seen.insert(key.clone());
+                (key.0, key.2)
+            } else {
+                (OVERFLOW_LABEL.to_string(), OVERFLOW_LABEL.to_string())
+            }
+        };
+
+        let labels = vec![
+            Label::new("kind", kind),
+            Label::new("status", error.code.as_str().to_string()),
+            Label::new("route", route),
+        ];
+        metrics::counter!(self.name.clone(), labels).increment(1);
+    }
+}
+
+impl Default for ErrorMetrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ErrorMetrics
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Transform = ErrorMetricsMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ErrorMetricsMiddleware {
+            service,
+            metrics: self.clone(),
+        }))
+    }
+}
+
+/// Service produced by [`ErrorMetrics`].
+pub struct ErrorMetricsMiddleware<S> {
+    service: S,
+    metrics: ErrorMetrics,
+}
+
+impl<S, B> Service<ServiceRequest> for ErrorMetricsMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let metrics = self.metrics.clone();
+        let fut = self.service.call(req);
+
+        Box::pin(async move {
+            let result = fut.await;
+            match &result {
+                Ok(res) => {
+                    let route = res.request().match_pattern();
+                    let route = route.as_deref().unwrap_or(UNMATCHED_ROUTE);
+                    if let Some(error) = ApiError::from_response_ext(res) {
+                        metrics.record(&error, route);
+                    } else if let Some(errors) = ApiErrors::from_response_ext(res) {
+                        errors.errors().iter().for_each(|error| metrics.record(error, route));
+                    }
+                }
+                // Errors returned by inner middleware (e.g. `CatchPanic`) are rendered later by actix-web.
+                Err(err) => {
+                    if let Some(error) = err.as_error::<ApiError>() {
+                        metrics.record(error, UNMATCHED_ROUTE);
+                    } else if let Some(errors) = err.as_error::<ApiErrors>() {
+                        errors.errors().iter().for_each(|error| metrics.record(error, UNMATCHED_ROUTE));
+                    }
+                }
+            }
+            result
+        })
+    }
+}
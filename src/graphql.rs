@@ -0,0 +1,33 @@
+use std::sync::Arc;
+
+use async_graphql::{ErrorExtensionValues, ErrorExtensions, Value};
+
+use crate::ApiError;
+
+/// Converts the error into a GraphQL error shaped as
+/// `{"message": ..., "extensions": {"code": kind, "id": id, "status": code, "details": ...}}`,
+/// `id` and `details` being omitted when unset.
+///
+/// async-graphql already converts every `Display` type into its `Error`, which keeps only
+/// the message, so use `.extend()` (or `ResultExt::extend` on a `Result`) to get the extensions.
+/// The `ApiError` is kept as the error source.
+impl ErrorExtensions for ApiError {
+    fn extend(&self) -> async_graphql::Error {
+        self.report();
+        let mut extensions = ErrorExtensionValues::default();
+        extensions.set("code", self.kind.as_str());
+        if let Some(id) = &self.id {
+            extensions.set("id", id.as_str());
+        }
+        extensions.set("status", self.code.as_u16());
+        if let Some(details) = &self.details {
+            // Every JSON value has a GraphQL representation, so this never falls back to null.
+            extensions.set("details", Value::from_json(details.clone()).unwrap_or(Value::Null));
+        }
+        async_graphql::Error {
+            message: self.message.clone(),
+            source: Some(Arc::new(self.clone())),
+            extensions: Some(extensions),
+        }
+    }
+}
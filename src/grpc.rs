@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+
+use tonic::{Code, Status};
+use tonic_types::{ErrorDetails, StatusExt};
+
+use crate::ApiError;
+
+/// Maps an HTTP status code to the closest gRPC status code.
+///
+/// | HTTP                        | gRPC                  |
+/// |-----------------------------|-----------------------|
+/// | 400, 422                    | `INVALID_ARGUMENT`    |
+/// | 401                         | `UNAUTHENTICATED`     |
+/// | 403                         | `PERMISSION_DENIED`   |
+/// | 404, 410                    | `NOT_FOUND`           |
+/// | 405, 501                    | `UNIMPLEMENTED`       |
+/// | 408, 504                    | `DEADLINE_EXCEEDED`   |
+/// | 409                         | `ALREADY_EXISTS`      |
+/// | 412                         | `FAILED_PRECONDITION` |
+/// | 413, 429                    | `RESOURCE_EXHAUSTED`  |
+/// | 416                         | `OUT_OF_RANGE`        |
+/// | 499                         | `CANCELLED`           |
+/// | 502, 503                    | `UNAVAILABLE`         |
+/// | other 4xx                   | `INVALID_ARGUMENT`    |
+/// | other 5xx                   | `INTERNAL`            |
+/// | anything else               | `UNKNOWN`             |
+pub fn grpc_code_for_status(status: u16) -> Code {
+    match status {
+        400 | 422 => Code::InvalidArgument,
+        401 => Code::Unauthenticated,
+        403 => Code::PermissionDenied,
+        404 | 410 => Code::NotFound,
+        405 | 501 => Code::Unimplemented,
+        408 | 504 => Code::DeadlineExceeded,
+        409 => Code::AlreadyExists,
+        412 => Code::FailedPrecondition,
+        413 | 429 => Code::ResourceExhausted,
+        416 => Code::OutOfRange,
+        499 => Code::Cancelled,
+        502 | 503 => Code::Unavailable,
+        400..=499 => Code::InvalidArgument,
+        500..=599 => Code::Internal,
+        _ => Code::Unknown,
+    }
+}
+
+impl ApiError {
+    /// The gRPC status code of this error: the one set with [`ApiError::with_grpc_code`]
+    /// (or the `grpc` attribute), otherwise the mapping of `code` by [`grpc_code_for_status`].
+    pub fn grpc_code(&self) -> Code {
+        match self.grpc_code {
+            Some(code) => Code::from_i32(code),
+            None => grpc_code_for_status(self.code.as_u16()),
+        }
+    }
+
+    /// Converts the error into a `tonic::Status` whose details hold a `google.rpc.ErrorInfo`.
+    ///
+    /// The `ErrorInfo` has the upper-cased `kind` as `reason` and the given `domain`
+    /// (normally the name of the service). Its metadata holds the original `kind`
+    /// and, if any, the `id` and the `details` serialized as JSON.
+    pub fn to_grpc_status(&self, domain: &str) -> Status {
+        let mut metadata = HashMap::new();
+        metadata.insert("kind".to_string(), self.kind.clone());
+        if let Some(id) = &self.id {
+            metadata.insert("id".to_string(), id.clone());
+        }
+        if let Some(details) = &self.details {
+            metadata.insert("details".to_string(), details.to_string());
+        }
+        let details = ErrorDetails::with_error_info(self.kind.to_uppercase(), domain, metadata);
+        Status::with_error_details(self.grpc_code(), self.message.clone(), details)
+    }
+}
+
+/// Converts the error with an empty `ErrorInfo` domain. Use [`ApiError::to_grpc_status`] to set one.
+impl From<ApiError> for Status {
+    fn from(error: ApiError) -> Self {
+        error.report();
+        error.to_grpc_status("")
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A source of localized message templates, looked up by locale and message key.
+///
+/// Templates may reference the error's arguments with `{name}` placeholders
+/// (use `{{` and `}}` for literal braces). See [`JsonCatalog`] for a file-based implementation.
+pub trait MessageCatalog: Send + Sync {
+    /// Returns the template for `key` in `locale` (e.g. `"fr"` or `"fr-ca"`), if the catalog has one.
+    fn message(&self, locale: &str, key: &str) -> Option<String>;
+}
+
+/// A [`MessageCatalog`] holding one JSON document per locale.
+///
+/// Nested objects are flattened into dotted keys, so `{"auth": {"invalid_password": "..."}}`
+/// provides the key `auth.invalid_password`. Locales are matched case-insensitively.
+#[derive(Debug, Clone, Default)]
+pub struct JsonCatalog {
+    messages: HashMap<String, HashMap<String, String>>,
+}
+
+impl JsonCatalog {
+    /// Creates an empty catalog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads every `<locale>.json` file of a directory, e.g. `locales/en.json` and `locales/fr.json`.
+    pub fn from_dir(dir: impl AsRef<Path>) -> std::io::Result<Self> {
+        let mut catalog = Self::new();
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+            let Some(locale) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let content = std::fs::read(&path)?;
+            let document: serde_json::Value = serde_json::from_slice(&content).map_err(|e| {
+                std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{}: {}", path.display(), e))
+            })?;
+            catalog.add_locale(locale, &document);
+        }
+        Ok(catalog)
+    }
+
+    /// Adds the messages of a JSON document to `locale`. Non-string leaves are ignored.
+    pub fn add_locale(&mut self, locale: &str, document: &serde_json::Value) -> &mut Self {
+        let messages = self.messages.entry(locale.to_lowercase()).or_default();
+        flatten(String::new(), document, messages);
+        self
+    }
+
+    /// Adds a single message template.
+    pub fn insert(&mut self, locale: &str, key: &str, template: &str) -> &mut Self {
+        self.messages
+            .entry(locale.to_lowercase())
+            .or_default()
+            .insert(key.to_string(), template.to_string());
+        self
+    }
+}
+
+fn flatten(prefix: String, value: &serde_json::Value, into: &mut HashMap<String, String>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map {
+                let key = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                flatten(key, value, into);
+            }
+        }
+        serde_json::Value::String(template) => {
+            into.insert(prefix, template.clone());
+        }
+        _ => {}
+    }
+}
+
+impl MessageCatalog for JsonCatalog {
+    fn message(&self, locale: &str, key: &str) -> Option<String> {
+        self.messages.get(&locale.to_lowercase())?.get(key).cloned()
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The severity at which an `ApiError` is reported when it is turned into a response.
+///
+/// Set per variant with `#[api_error(log = "warn")]` (or its alias `level`) or on an `ApiError` with
+/// [`ApiError::with_level`](crate::ApiError::with_level). When unset, the level is derived
+/// from the status code with [`Level::for_status`]. `Level::Off` suppresses reporting entirely.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    Off,
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    /// The default level for a status code: `Error` for 5xx, `Info` for 4xx and `Debug` otherwise.
+    pub fn for_status(code: u16) -> Self {
+        match code {
+            500..=599 => Level::Error,
+            400..=499 => Level::Info,
+            _ => Level::Debug,
+        }
+    }
+
+    /// The lowercase name of the level, as accepted by the `log` and `level` attributes.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Level::Off => "off",
+            Level::Debug => "debug",
+            Level::Info => "info",
+            Level::Warn => "warn",
+            Level::Error => "error",
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(Level::Off),
+            "debug" => Ok(Level::Debug),
+            "info" => Ok(Level::Info),
+            "warn" => Ok(Level::Warn),
+            "error" => Ok(Level::Error),
+            _ => Err(format!("unknown level \"{}\", expected one of: off, debug, info, warn, error", s)),
+        }
+    }
+}
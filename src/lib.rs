@@ -1,21 +1,113 @@
 use std::fmt::{Display, Formatter, Debug};
 use std::error::Error;
-use serde::Serialize;
+use std::sync::Arc;
+use serde::ser::{Serialize, SerializeMap, Serializer};
 pub use actix_error_derive::AsApiError;
 
+#[cfg(feature = "anyhow")]
+mod any_error;
+#[cfg(feature = "axum")]
+mod axum_response;
+mod builder;
+mod catalog;
+mod collection;
+mod config;
+mod conversions;
+#[cfg(any(feature = "sqlx", feature = "diesel"))]
+mod database;
+#[cfg(feature = "async-graphql")]
+mod graphql;
+#[cfg(feature = "tonic")]
+mod grpc;
+#[cfg(all(feature = "metrics", feature = "actix"))]
+mod error_metrics;
+mod i18n;
+mod level;
+#[cfg(feature = "actix")]
+mod localize;
+#[cfg(feature = "log-errors")]
+mod logging;
+#[cfg(feature = "actix")]
+mod panic;
+#[cfg(feature = "actix")]
+mod render;
+#[cfg(feature = "tracing")]
+mod trace;
+#[cfg(feature = "validator")]
+mod validate;
+#[cfg(all(feature = "validator", feature = "actix"))]
+mod validated_json;
+mod validation;
+#[cfg(feature = "anyhow")]
+pub use any_error::{AnyApiError, ApiContext};
+pub use builder::ApiErrorBuilder;
+pub use catalog::{CatalogStatus, ErrorDescriptor};
+pub use collection::{ApiErrors, ErrorsFormat, StatusPolicy};
+pub use config::{ErrorConfig, Naming};
+#[cfg(all(feature = "metrics", feature = "actix"))]
+pub use error_metrics::{ErrorMetrics, ErrorMetricsMiddleware};
+#[cfg(feature = "tonic")]
+pub use grpc::grpc_code_for_status;
+pub use i18n::{JsonCatalog, MessageCatalog};
+#[cfg(feature = "actix")]
+pub use localize::{Localize, LocalizeMiddleware};
+pub use level::Level;
+#[cfg(feature = "actix")]
+pub use panic::{CatchPanic, CatchPanicMiddleware};
+#[cfg(feature = "actix")]
+pub use render::{ErrorRenderer, ErrorRendererMiddleware};
+#[cfg(all(feature = "validator", feature = "actix"))]
+pub use validated_json::ValidatedJson;
+pub use validation::{ValidationErrors, ValidationIssue};
+pub use http::{status::InvalidStatusCode, StatusCode};
+
+/// Used by the code generated by the derive macro. Not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    #[cfg(feature = "actix")]
+    pub use actix_web;
+    #[cfg(feature = "async-graphql")]
+    pub use async_graphql;
+    #[cfg(feature = "axum")]
+    pub use axum;
+    pub use serde_json;
+    #[cfg(feature = "tonic")]
+    pub use tonic;
+}
+
 /// Represents a structured error that can be easily serialized and sent as an HTTP response.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone)]
 pub struct ApiError {
     /// A machine-readable error type or category.
     pub kind: String,
-    /// The HTTP status code associated with this error. This field is not serialized.
-    #[serde(skip_serializing)]
-    pub code: u16,
+    /// A stable identifier of the error, e.g. `E1042`. Unlike `kind`, it is never derived from
+    /// a variant name, so it can be kept when variants are renamed. Serialized as `id` if set.
+    pub id: Option<String>,
+    /// The HTTP status code associated with this error. It is only serialized, as `status`,
+    /// if [`ApiError::with_status_in_body`] was called.
+    pub code: StatusCode,
     /// A human-readable message describing the error.
     pub message: String,
     /// Optional structured details about the error.
-    #[serde(skip_serializing_if = "Option::is_none")]
     pub details: Option<serde_json::Value>,
+    /// The underlying error that caused this one, if any. This field is not serialized.
+    pub source: Option<Arc<dyn Error + Send + Sync>>,
+    /// The level at which this error is reported. Defaults to a level based on `code`. This field is not serialized.
+    pub level: Option<Level>,
+    /// The key of the message in a [`MessageCatalog`], used to localize `message`. This field is not serialized.
+    pub message_key: Option<String>,
+    /// Named arguments interpolated into the localized message. This field is not serialized.
+    pub message_args: Vec<(String, String)>,
+    /// The numeric gRPC status code overriding the one derived from `code` when the error
+    /// is converted into a `tonic::Status`. This field is not serialized.
+    pub grpc_code: Option<i32>,
+    /// Headers added to the response, e.g. `Retry-After`. This field is not serialized.
+    pub headers: http::HeaderMap,
+    /// Whether the numeric status is serialized in the body, as `status`. This field is not serialized.
+    pub status_in_body: bool,
+    /// The database constraint whose violation caused this error, if any. It is only added to the
+    /// details by an [`ErrorConfig`] with [`ErrorConfig::constraint_names`] enabled.
+    pub constraint: Option<String>,
 }
 
 impl ApiError {
@@ -27,19 +119,145 @@ impl ApiError {
     /// * `kind` - A string slice representing the kind or category of the error.
     /// * `message` - A `String` containing the descriptive message for the error.
     /// * `details` - Optional `serde_json::Value` for structured error details.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `code` is not a valid status code (between 100 and 999).
+    /// Use [`ApiError::try_new`] for codes that are not known in advance, or [`ApiError::with_status`]
+    /// to pass a [`StatusCode`].
+    #[track_caller]
     pub fn new(
         code: u16,
         kind: &str,
         message: String,
         details: Option<serde_json::Value>,
+    ) -> Self {
+        match Self::try_new(code, kind, message, details) {
+            Ok(error) => error,
+            Err(_) => panic!("invalid status code {} for ApiError {}", code, kind),
+        }
+    }
+
+    /// Creates a new `ApiError`, or fails if `code` is not a valid status code (between 100 and 999).
+    pub fn try_new(
+        code: u16,
+        kind: &str,
+        message: String,
+        details: Option<serde_json::Value>,
+    ) -> Result<Self, InvalidStatusCode> {
+        Ok(Self::with_status(StatusCode::from_u16(code)?, kind, message, details))
+    }
+
+    /// Creates a new `ApiError` from a `StatusCode`.
+    pub fn with_status(
+        code: StatusCode,
+        kind: &str,
+        message: String,
+        details: Option<serde_json::Value>,
     ) -> Self {
         Self {
             kind: kind.to_string(),
+            id: None,
             message,
             code,
             details,
+            source: None,
+            level: None,
+            message_key: None,
+            message_args: Vec::new(),
+            grpc_code: None,
+            headers: http::HeaderMap::new(),
+            status_in_body: false,
+            constraint: None,
+        }
+    }
+
+    /// Attaches the underlying error that caused this `ApiError`.
+    ///
+    /// The source is never sent to the client, but it is returned by `Error::source`
+    /// and kept in the response extensions (see [`ApiError::from_response_ext`]).
+    pub fn with_source<E: Error + Send + Sync + 'static>(mut self, source: E) -> Self {
+        self.source = Some(Arc::new(source));
+        self
+    }
+
+    /// Sets the level at which this error is reported, overriding the default for its status code.
+    pub fn with_level(mut self, level: Level) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// Sets the catalog key used to localize the message, and the arguments interpolated into it.
+    ///
+    /// `message` is kept as the fallback when no catalog entry matches the requested locale.
+    /// See [`Localize`].
+    pub fn with_message_key(mut self, key: &str, args: Vec<(String, String)>) -> Self {
+        self.message_key = Some(key.to_string());
+        self.message_args = args;
+        self
+    }
+
+    /// Sets the stable identifier of the error, e.g. `E1042`.
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+
+    /// Includes the numeric status in the serialized body, e.g. `{"kind": ..., "message": ..., "status": 404}`.
+    pub fn with_status_in_body(mut self) -> Self {
+        self.status_in_body = true;
+        self
+    }
+
+    /// Sets the gRPC status code used instead of the one mapped from the HTTP status,
+    /// e.g. `tonic::Code::FailedPrecondition as i32`. Only used with the `tonic` feature.
+    pub fn with_grpc_code(mut self, code: i32) -> Self {
+        self.grpc_code = Some(code);
+        self
+    }
+
+    /// The level at which this error is reported, as set by [`ApiError::with_level`]
+    /// or derived from the status code with [`Level::for_status`].
+    pub fn level(&self) -> Level {
+        self.level.unwrap_or_else(|| Level::for_status(self.code.as_u16()))
+    }
+
+    /// Reports the error to the enabled instrumentation backends (the `tracing` and `log-errors` features).
+    ///
+    /// This is done automatically when the error is turned into a response; call it to report
+    /// errors that are handled without being sent to a client.
+    pub fn report(&self) {
+        #[cfg(feature = "tracing")]
+        trace::record(self);
+        #[cfg(feature = "log-errors")]
+        logging::record(self);
+    }
+
+    /// Joins the messages of the source chain, outermost first.
+    #[cfg(any(feature = "tracing", feature = "log-errors"))]
+    pub(crate) fn source_chain(&self) -> Option<String> {
+        let mut current = Error::source(self);
+        let mut chain = Vec::new();
+        while let Some(source) = current {
+            chain.push(source.to_string());
+            current = source.source();
+        }
+        if chain.is_empty() {
+            None
+        } else {
+            Some(chain.join(": "))
         }
     }
+
+    /// Retrieves the `ApiError` that produced a response.
+    ///
+    /// `ResponseError::error_response` (and `IntoResponse::into_response` with the `axum` feature) stores a clone of the `ApiError` in the response extensions,
+    /// so middleware can inspect the kind, status, message and source of an error without
+    /// parsing the serialized body. Returns `None` if the response was not produced by an `ApiError`.
+    #[cfg(feature = "actix")]
+    pub fn from_response_ext<B>(res: &actix_web::dev::ServiceResponse<B>) -> Option<ApiError> {
+        res.response().extensions().get::<ApiError>().cloned()
+    }
 }
 
 /// A trait for types that can be converted into an `ApiError`.
@@ -48,21 +266,57 @@ pub trait AsApiErrorTrait {
     fn as_api_error(&self) -> ApiError;
 }
 
+impl AsApiErrorTrait for ApiError {
+    fn as_api_error(&self) -> ApiError {
+        self.clone()
+    }
+}
+
+impl Serialize for ApiError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("kind", &self.kind)?;
+        if let Some(id) = &self.id {
+            map.serialize_entry("id", id)?;
+        }
+        map.serialize_entry("message", &self.message)?;
+        if let Some(details) = &self.details {
+            map.serialize_entry("details", details)?;
+        }
+        if self.status_in_body {
+            map.serialize_entry("status", &self.code.as_u16())?;
+        }
+        map.end()
+    }
+}
+
 impl Display for ApiError {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         write!(f, "{}: {}", self.kind, self.message)
     }
 }
 
-impl Error for ApiError {}
+impl Error for ApiError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_deref().map(|e| e as &(dyn Error + 'static))
+    }
+}
 
+#[cfg(feature = "actix")]
 impl actix_web::ResponseError for ApiError {
     fn status_code(&self) -> actix_web::http::StatusCode {
-        actix_web::http::StatusCode::from_u16(self.code)
-            .unwrap_or(actix_web::http::StatusCode::INTERNAL_SERVER_ERROR)
+        // actix-web uses its own `StatusCode` type, which accepts the same range of codes.
+        actix_web::http::StatusCode::from_u16(self.code.as_u16()).expect("status codes are validated by ApiError")
     }
 
     fn error_response(&self) -> actix_web::HttpResponse {
-        actix_web::HttpResponse::build(self.status_code()).json(self)
+        self.report();
+        let mut builder = actix_web::HttpResponse::build(self.status_code());
+        for (name, value) in &self.headers {
+            builder.append_header((name.as_str(), value.as_bytes()));
+        }
+        let mut response = builder.json(self);
+        response.extensions_mut().insert(self.clone());
+        response
     }
 }
@@ -0,0 +1,195 @@
+use std::future::{ready, Ready};
+use std::sync::Arc;
+
+use actix_web::body::{BoxBody, EitherBody, MessageBody};
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::header::{AcceptLanguage, Header, HeaderValue, Preference, CONTENT_LANGUAGE, VARY};
+use futures_util::future::LocalBoxFuture;
+
+use crate::render::{render_body, render_errors_body};
+use crate::{ApiError, ApiErrors, MessageCatalog};
+
+/// Replaces the `{name}` placeholders of `template` with the matching arguments.
+/// Unknown placeholders are kept as is.
+fn interpolate(template: &str, args: &[(String, String)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(i) = rest.find(['{', '}']) {
+        out.push_str(&rest[..i]);
+        rest = &rest[i..];
+        if rest.starts_with("{{") || rest.starts_with("}}") {
+            out.push_str(&rest[..1]);
+            rest = &rest[2..];
+            continue;
+        }
+        let name_end = if rest.starts_with('{') { rest.find('}') } else { None };
+        match name_end.and_then(|end| args.iter().find(|(name, _)| *name == rest[1..end]).map(|a| (end, a))) {
+            Some((end, (_, value))) => {
+                out.push_str(value);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push_str(&rest[..1]);
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Middleware localizing the message of `ApiError` responses.
+///
+/// For responses produced by an error with a message key (`#[api_error(msg_key = "...")]` or
+/// [`ApiError::with_message_key`]), the locales listed in the request's `Accept-Language` header
+/// are tried in order of preference (a regional tag such as `fr-CA` falls back to `fr`), then the
+/// default locale. The first template found in the catalog replaces the message, with the error's
+/// arguments interpolated, and the `Content-Language` header is set. When no template matches,
+/// the response keeps its default message. Either way, `Vary: Accept-Language` is added so that
+/// caches keep one response per language. Each error of an [`ApiErrors`] response is translated
+/// the same way, `Content-Language` being the locale of the first translated message.
+///
+/// ```rust
+/// use actix_web::App;
+/// use actix_error::{JsonCatalog, Localize};
+///
+/// let mut catalog = JsonCatalog::new();
+/// catalog.insert("fr", "auth.invalid_password", "Mot de passe invalide");
+/// let app = App::new().wrap(Localize::new(catalog).default_locale("en"));
+/// ```
+#[derive(Clone)]
+pub struct Localize {
+    catalog: Arc<dyn MessageCatalog>,
+    default_locale: Option<String>,
+}
+
+impl Localize {
+    /// Creates the middleware looking up messages in `catalog`.
+    pub fn new(catalog: impl MessageCatalog + 'static) -> Self {
+        Self {
+            catalog: Arc::new(catalog),
+            default_locale: None,
+        }
+    }
+
+    /// Sets the locale used when none of the requested locales has a matching message.
+    pub fn default_locale(mut self, locale: &str) -> Self {
+        self.default_locale = Some(locale.to_string());
+        self
+    }
+
+    /// Finds the first candidate locale having a template for `key`.
+    fn lookup(&self, locales: &[String], key: &str) -> Option<(String, String)> {
+        locales
+            .iter()
+            .chain(self.default_locale.as_ref())
+            .find_map(|locale| self.catalog.message(locale, key).map(|t| (locale.clone(), t)))
+    }
+
+    /// Translates the message of `error`, returning the locale of the template used.
+    fn translate(&self, error: &mut ApiError, locales: &[String]) -> Option<String> {
+        let (locale, template) = error.message_key.as_deref().and_then(|key| self.lookup(locales, key))?;
+        error.message = interpolate(&template, &error.message_args);
+        Some(locale)
+    }
+}
+
+/// The locales accepted by the request, most preferred first, each followed by its primary language.
+fn requested_locales(req: &ServiceRequest) -> Vec<String> {
+    let mut locales = Vec::new();
+    if let Ok(accept) = AcceptLanguage::parse(req) {
+        for preference in accept.ranked() {
+            if let Preference::Specific(tag) = preference {
+                for locale in [tag.as_str(), tag.primary_language()] {
+                    if !locales.iter().any(|l: &String| l.eq_ignore_ascii_case(locale)) {
+                        locales.push(locale.to_string());
+                    }
+                }
+            }
+        }
+    }
+    locales
+}
+
+/// Marks the response as depending on the `Accept-Language` header, keeping the other `Vary` values.
+fn vary_on_language<B>(res: &mut ServiceResponse<B>) {
+    res.headers_mut().append(VARY, HeaderValue::from_static("accept-language"));
+}
+
+impl<S, B> Transform<S, ServiceRequest> for Localize
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = actix_web::Error;
+    type Transform = LocalizeMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(LocalizeMiddleware {
+            service,
+            localize: self.clone(),
+        }))
+    }
+}
+
+/// Service produced by [`Localize`].
+pub struct LocalizeMiddleware<S> {
+    service: S,
+    localize: Localize,
+}
+
+impl<S, B> Service<ServiceRequest> for LocalizeMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let localize = self.localize.clone();
+        let locales = requested_locales(&req);
+        let fut = self.service.call(req);
+
+        Box::pin(async move {
+            let mut res = fut.await?;
+            let (locale, body) = if let Some(mut error) = ApiError::from_response_ext(&res) {
+                if error.message_key.is_some() {
+                    vary_on_language(&mut res);
+                }
+                let Some(locale) = localize.translate(&mut error, &locales) else {
+                    return Ok(res.map_into_left_body());
+                };
+                let body = render_body(&error, res.request())?;
+                res.response_mut().extensions_mut().insert(error);
+                (locale, body)
+            } else if let Some(mut errors) = ApiErrors::from_response_ext(&res) {
+                if errors.errors().iter().any(|error| error.message_key.is_some()) {
+                    vary_on_language(&mut res);
+                }
+                let translated: Vec<String> =
+                    errors.errors_mut().iter_mut().filter_map(|error| localize.translate(error, &locales)).collect();
+                let Some(locale) = translated.into_iter().next() else {
+                    return Ok(res.map_into_left_body());
+                };
+                let body = render_errors_body(&errors, res.request())?;
+                res.response_mut().extensions_mut().insert(errors);
+                (locale, body)
+            } else {
+                return Ok(res.map_into_left_body());
+            };
+
+            if let Ok(value) = HeaderValue::from_str(&locale) {
+                res.headers_mut().insert(CONTENT_LANGUAGE, value);
+            }
+            Ok(res.map_body(|_, _| EitherBody::right(BoxBody::new(body))))
+        })
+    }
+}
+
@@ -0,0 +1,28 @@
+use crate::{ApiError, Level};
+
+/// Logs `error` through the `log` crate at its reporting level.
+pub(crate) fn record(error: &ApiError) {
+    let level = match error.level() {
+        Level::Off => return,
+        Level::Debug => log::Level::Debug,
+        Level::Info => log::Level::Info,
+        Level::Warn => log::Level::Warn,
+        Level::Error => log::Level::Error,
+    };
+    if !log::log_enabled!(target: "actix_error", level) {
+        return;
+    }
+
+    let details = error.details.as_ref().map(|d| format!(" details={}", d)).unwrap_or_default();
+    let source = error.source_chain().map(|s| format!(" source=\"{}\"", s)).unwrap_or_default();
+    log::log!(
+        target: "actix_error",
+        level,
+        "{} {}: {}{}{}",
+        error.code.as_u16(),
+        error.kind,
+        error.message,
+        details,
+        source,
+    );
+}
@@ -0,0 +1,145 @@
+use std::any::Any;
+use std::cell::RefCell;
+use std::future::{ready, Ready};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::{Arc, Once};
+
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use futures_util::future::{FutureExt, LocalBoxFuture};
+
+use crate::ApiError;
+
+static INSTALL_HOOK: Once = Once::new();
+
+thread_local! {
+    /// Location of the last panic observed on this thread, recorded by the hook installed by `CatchPanic`.
+    static LAST_PANIC_LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Chains a panic hook that remembers where the panic happened, so the middleware can log it.
+/// The previously installed hook is still called afterwards.
+fn install_location_hook() {
+    INSTALL_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let location = info.location().map(|l| l.to_string());
+            LAST_PANIC_LOCATION.with(|last| *last.borrow_mut() = location);
+            previous(info);
+        }));
+    });
+}
+
+/// Extracts a printable message from a panic payload.
+fn payload_message(payload: &(dyn Any + Send)) -> &str {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.as_str()
+    } else {
+        "Box<dyn Any>"
+    }
+}
+
+/// Middleware catching panics raised by the wrapped service and turning them into an `ApiError` response.
+///
+/// Without it, a panicking handler drops the connection. With it, the panic payload and location
+/// are logged at the `error` level and the client receives the configured error, which by default
+/// is a `500` with `kind = "internal_panic"` and a message that does not leak the panic payload.
+///
+/// ```rust
+/// use actix_web::App;
+/// use actix_error::CatchPanic;
+///
+/// let app = App::new().wrap(CatchPanic::default());
+/// ```
+#[derive(Debug, Clone)]
+pub struct CatchPanic {
+    error: Arc<ApiError>,
+}
+
+impl CatchPanic {
+    /// Creates the middleware, responding with `error` whenever a handler panics.
+    pub fn new(error: ApiError) -> Self {
+        Self { error: Arc::new(error) }
+    }
+}
+
+impl Default for CatchPanic {
+    fn default() -> Self {
+        Self::new(ApiError::new(500, "internal_panic", "Internal server error".to_string(), None))
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for CatchPanic
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Transform = CatchPanicMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        install_location_hook();
+        ready(Ok(CatchPanicMiddleware {
+            service,
+            error: Arc::clone(&self.error),
+        }))
+    }
+}
+
+/// Service produced by [`CatchPanic`].
+pub struct CatchPanicMiddleware<S> {
+    service: S,
+    error: Arc<ApiError>,
+}
+
+impl<S, B> Service<ServiceRequest> for CatchPanicMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let method = req.method().clone();
+        let path = req.path().to_owned();
+        let error = Arc::clone(&self.error);
+
+        // The wrapped service may panic while building its future, not only while polling it.
+        let fut = panic::catch_unwind(AssertUnwindSafe(|| self.service.call(req)));
+
+        Box::pin(async move {
+            let result = match fut {
+                Ok(fut) => AssertUnwindSafe(fut).catch_unwind().await,
+                Err(payload) => Err(payload),
+            };
+
+            match result {
+                Ok(res) => res,
+                Err(payload) => {
+                    let location = LAST_PANIC_LOCATION
+                        .with(|last| last.borrow_mut().take())
+                        .unwrap_or_else(|| "<unknown>".to_string());
+                    log::error!(
+                        "handler for {} {} panicked at {}: {}",
+                        method,
+                        path,
+                        location,
+                        payload_message(payload.as_ref()),
+                    );
+                    // The request was moved into the panicked service, so the error is handed back
+                    // to actix-web which renders it through `ResponseError::error_response`.
+                    Err(ApiError::clone(&error).into())
+                }
+            }
+        })
+    }
+}
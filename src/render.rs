@@ -0,0 +1,94 @@
+use std::future::{ready, Ready};
+
+use actix_web::body::{BoxBody, EitherBody, MessageBody};
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::HttpRequest;
+use futures_util::future::LocalBoxFuture;
+
+use crate::{ApiError, ApiErrors, ErrorConfig};
+
+/// Serializes the error with the `ErrorConfig` registered for the request, if any.
+pub(crate) fn render_body(error: &ApiError, req: &HttpRequest) -> serde_json::Result<Vec<u8>> {
+    match req.app_data::<ErrorConfig>() {
+        Some(config) => serde_json::to_vec(&config.render(error, req.method().as_str(), req.path())),
+        None => serde_json::to_vec(error),
+    }
+}
+
+/// Serializes the errors with the `ErrorConfig` registered for the request, if any.
+pub(crate) fn render_errors_body(errors: &ApiErrors, req: &HttpRequest) -> serde_json::Result<Vec<u8>> {
+    match req.app_data::<ErrorConfig>() {
+        Some(config) => serde_json::to_vec(&errors.render(config, req.method().as_str(), req.path())),
+        None => serde_json::to_vec(errors),
+    }
+}
+
+/// Middleware rendering `ApiError` (and [`ApiErrors`]) responses with the [`ErrorConfig`] found in the app data.
+///
+/// The configuration is looked up once the request was routed, so a scope or resource can
+/// register its own. Responses are left untouched when no configuration is registered.
+/// Each error of an [`ApiErrors`] response is rendered with the configuration, the envelope
+/// wrapping the whole body.
+///
+/// ```rust
+/// use actix_web::App;
+/// use actix_error::{ErrorConfig, ErrorRenderer};
+///
+/// let app = App::new()
+///     .app_data(ErrorConfig::new().status(true).timestamp(true).instance_id("api-eu-1"))
+///     .wrap(ErrorRenderer);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ErrorRenderer;
+
+impl<S, B> Transform<S, ServiceRequest> for ErrorRenderer
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = actix_web::Error;
+    type Transform = ErrorRendererMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ErrorRendererMiddleware { service }))
+    }
+}
+
+/// Service produced by [`ErrorRenderer`].
+pub struct ErrorRendererMiddleware<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for ErrorRendererMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    actix_web::dev::forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let fut = self.service.call(req);
+
+        Box::pin(async move {
+            let res = fut.await?;
+            if res.request().app_data::<ErrorConfig>().is_none_or(ErrorConfig::is_default) {
+                return Ok(res.map_into_left_body());
+            }
+            let body = if let Some(error) = ApiError::from_response_ext(&res) {
+                render_body(&error, res.request())?
+            } else if let Some(errors) = ApiErrors::from_response_ext(&res) {
+                render_errors_body(&errors, res.request())?
+            } else {
+                return Ok(res.map_into_left_body());
+            };
+            Ok(res.map_body(|_, _| EitherBody::right(BoxBody::new(body))))
+        })
+    }
+}
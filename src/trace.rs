@@ -0,0 +1,30 @@
+use crate::{ApiError, Level};
+
+/// Records `error` as an event on the current span.
+pub(crate) fn record(error: &ApiError) {
+    let details = error.details.as_ref().map(|d| d.to_string());
+    let source = error.source_chain();
+
+    macro_rules! emit {
+        ($level:expr) => {
+            tracing::event!(
+                $level,
+                error.kind = %error.kind,
+                http.status_code = error.code.as_u16(),
+                error.message = %error.message,
+                error.details = details.as_deref(),
+                error.source = source.as_deref(),
+                "request failed with {}",
+                error.kind,
+            )
+        };
+    }
+
+    match error.level() {
+        Level::Off => {}
+        Level::Debug => emit!(tracing::Level::DEBUG),
+        Level::Info => emit!(tracing::Level::INFO),
+        Level::Warn => emit!(tracing::Level::WARN),
+        Level::Error => emit!(tracing::Level::ERROR),
+    }
+}
@@ -0,0 +1,42 @@
+use validator::ValidationErrorsKind;
+
+use crate::{ValidationErrors, ValidationIssue};
+
+/// Adds the issues of `errors` to `into`, nested under `prefix`.
+fn collect(prefix: &str, errors: &validator::ValidationErrors, into: &mut ValidationErrors) {
+    for (field, kind) in errors.errors() {
+        let path = if prefix.is_empty() { field.to_string() } else { format!("{}.{}", prefix, field) };
+        match kind {
+            ValidationErrorsKind::Field(errors) => {
+                for error in errors {
+                    let message = error.message.as_deref().unwrap_or(&error.code);
+                    let mut issue = ValidationIssue::new(&error.code, message);
+                    // `value` holds the rejected input, which must not be echoed back (think passwords).
+                    for (name, value) in error.params.iter().filter(|(name, _)| *name != "value") {
+                        issue = issue.param(name, value.clone());
+                    }
+                    into.add(&path, issue);
+                }
+            }
+            ValidationErrorsKind::Struct(errors) => collect(&path, errors, into),
+            ValidationErrorsKind::List(items) => {
+                for (index, errors) in items {
+                    collect(&format!("{}[{}]", path, index), errors, into);
+                }
+            }
+        }
+    }
+}
+
+/// Converts the errors of the `validator` crate, flattening nested structs into dotted paths
+/// (`address.city`) and lists into indexed paths (`items[2].name`).
+///
+/// Issues without a message use their code as message. The `value` parameter added by
+/// `validator` is dropped, so the rejected input is never sent back to the client.
+impl From<validator::ValidationErrors> for ValidationErrors {
+    fn from(errors: validator::ValidationErrors) -> Self {
+        let mut converted = ValidationErrors::new();
+        collect("", &errors, &mut converted);
+        converted
+    }
+}
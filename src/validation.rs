@@ -0,0 +1,183 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use serde::Serialize;
+
+use crate::{ApiError, AsApiErrorTrait};
+
+/// A single problem found while validating a field.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ValidationIssue {
+    /// A machine-readable code for the problem, e.g. `"too_short"`.
+    pub code: String,
+    /// A human-readable description of the problem.
+    pub message: String,
+    /// Values describing the problem, e.g. `{"min": 8}`. Serialized as an object, empty if there are none.
+    pub params: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ValidationIssue {
+    /// Creates an issue with no parameters.
+    pub fn new(code: &str, message: impl Into<String>) -> Self {
+        Self {
+            code: code.to_string(),
+            message: message.into(),
+            params: serde_json::Map::new(),
+        }
+    }
+
+    /// Adds a parameter to the issue.
+    pub fn param(mut self, name: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.params.insert(name.to_string(), value.into());
+        self
+    }
+}
+
+/// The issues found while validating a request, grouped by field path.
+///
+/// It converts into an `ApiError` with status `422`, `kind = "validation_failed"` and the issues
+/// in `details`, using a stable structure:
+///
+/// ```json
+/// {
+///     "kind": "validation_failed",
+///     "message": "Validation failed",
+///     "details": {
+///         "fields": {
+///             "password": [{"code": "too_short", "message": "Password is too short", "params": {"min": 8}}]
+///         }
+///     }
+/// }
+/// ```
+///
+/// Issues are typically accumulated in a handler, which then bails out with [`ValidationErrors::into_result`]:
+///
+/// ```rust
+/// use actix_error::{ValidationErrors, ValidationIssue};
+///
+/// fn validate(email: &str, password: &str) -> Result<(), ValidationErrors> {
+///     let mut errors = ValidationErrors::new();
+///     if !email.contains('@') {
+///         errors.add("email", ValidationIssue::new("invalid_email", "Email is invalid"));
+///     }
+///     if password.len() < 8 {
+///         errors.add("password", ValidationIssue::new("too_short", "Password is too short").param("min", 8));
+///     }
+///     errors.into_result()
+/// }
+///
+/// assert!(validate("ada@example.com", "correct horse").is_ok());
+/// assert_eq!(validate("ada", "short").unwrap_err().len(), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ValidationErrors {
+    fields: BTreeMap<String, Vec<ValidationIssue>>,
+}
+
+impl ValidationErrors {
+    /// Creates an empty set of errors.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an issue to the field at `path` (e.g. `"email"` or `"address.city"`).
+    pub fn add(&mut self, path: &str, issue: ValidationIssue) -> &mut Self {
+        self.fields.entry(path.to_string()).or_default().push(issue);
+        self
+    }
+
+    /// Adds an issue to the field at `path`, consuming and returning `self` for chaining.
+    pub fn with(mut self, path: &str, issue: ValidationIssue) -> Self {
+        self.add(path, issue);
+        self
+    }
+
+    /// Adds an issue to the field at `path` unless `valid` is true.
+    pub fn check(&mut self, valid: bool, path: &str, issue: impl FnOnce() -> ValidationIssue) -> &mut Self {
+        if !valid {
+            self.add(path, issue());
+        }
+        self
+    }
+
+    /// Adds all the issues of `other`, prefixing their paths with `prefix` and a dot.
+    /// An empty prefix merges the paths unchanged.
+    pub fn merge(&mut self, prefix: &str, other: ValidationErrors) -> &mut Self {
+        for (path, issues) in other.fields {
+            let path = if prefix.is_empty() { path } else { format!("{}.{}", prefix, path) };
+            self.fields.entry(path).or_default().extend(issues);
+        }
+        self
+    }
+
+    /// Returns true if no issue was added.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// The number of issues, all fields included.
+    pub fn len(&self) -> usize {
+        self.fields.values().map(Vec::len).sum()
+    }
+
+    /// The issues of the field at `path`.
+    pub fn field(&self, path: &str) -> &[ValidationIssue] {
+        self.fields.get(path).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// The issues, grouped by field path.
+    pub fn fields(&self) -> &BTreeMap<String, Vec<ValidationIssue>> {
+        &self.fields
+    }
+
+    /// Returns `Ok(())` if there are no issues, `Err(self)` otherwise.
+    pub fn into_result(self) -> Result<(), ValidationErrors> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self)
+        }
+    }
+}
+
+impl AsApiErrorTrait for ValidationErrors {
+    fn as_api_error(&self) -> ApiError {
+        ApiError::new(
+            422,
+            "validation_failed",
+            "Validation failed".to_string(),
+            Some(serde_json::json!({ "fields": self.fields })),
+        )
+    }
+}
+
+impl From<ValidationErrors> for ApiError {
+    fn from(errors: ValidationErrors) -> Self {
+        errors.as_api_error()
+    }
+}
+
+impl Display for ValidationErrors {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Validation failed:")?;
+        for (path, issues) in &self.fields {
+            for issue in issues {
+                write!(f, " {}: {};", path, issue.message)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ValidationErrors {}
+
+#[cfg(feature = "actix")]
+impl actix_web::ResponseError for ValidationErrors {
+    fn status_code(&self) -> actix_web::http::StatusCode {
+        self.as_api_error().status_code()
+    }
+
+    fn error_response(&self) -> actix_web::HttpResponse {
+        self.as_api_error().error_response()
+    }
+}
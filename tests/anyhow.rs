@@ -0,0 +1,82 @@
+#![cfg(all(feature = "anyhow", feature = "actix"))]
+
+use actix_error::*;
+use actix_web::ResponseError;
+use anyhow::Context;
+
+#[derive(AsApiError, Debug, thiserror::Error)]
+pub enum StockError {
+    #[api_error(status = "Conflict", kind = "out_of_stock")]
+    #[error("Item {0} is out of stock")]
+    OutOfStock(u32),
+}
+
+#[derive(AsApiError, Debug, thiserror::Error)]
+pub enum UnregisteredError {
+    #[api_error(status = "Gone")]
+    #[error("Gone")]
+    Gone,
+}
+
+/// Renders the error, returning the status and the JSON body.
+async fn render(error: AnyApiError) -> (u16, serde_json::Value) {
+    let res = error.error_response();
+    let status = res.status().as_u16();
+    let bytes = actix_web::body::to_bytes(res.into_body()).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[actix_web::test]
+async fn test_api_error_in_chain() {
+    let api_error = ApiError::new(404, "user_not_found", "User not found".to_string(), None);
+    let error: AnyApiError = anyhow::Error::new(api_error.clone()).context("loading profile").into();
+    assert_eq!(render(error).await, (404, serde_json::json!({"kind": "user_not_found", "message": "User not found"})));
+
+    // As the context of another error.
+    let error: AnyApiError = Err::<(), _>(std::io::Error::other("disk")).context(api_error).unwrap_err().into();
+    assert_eq!(render(error).await.0, 404);
+
+    let validation = ValidationErrors::new().with("email", ValidationIssue::new("invalid_email", "Email is invalid"));
+    assert_eq!(render(anyhow::Error::new(validation).into()).await.0, 422);
+}
+
+#[actix_web::test]
+async fn test_registered_types() {
+    let error = || -> AnyApiError { anyhow::Error::new(StockError::OutOfStock(3)).context("checkout").into() };
+    assert_eq!(render(error()).await.0, 500);
+
+    AnyApiError::register::<StockError>();
+    AnyApiError::register::<StockError>();
+    assert_eq!(
+        render(error()).await,
+        (409, serde_json::json!({"kind": "out_of_stock", "message": "Item 3 is out of stock"}))
+    );
+
+    assert_eq!(render(UnregisteredError::Gone.into()).await.0, 500);
+}
+
+#[actix_web::test]
+async fn test_redacted_internal_error() {
+    let error: AnyApiError = anyhow::anyhow!("password=hunter2").context("connecting to database").into();
+    let api_error = error.api_error();
+    assert_eq!(
+        std::error::Error::source(&api_error).unwrap().to_string(),
+        "connecting to database: password=hunter2"
+    );
+    assert_eq!(
+        render(error).await,
+        (500, serde_json::json!({"kind": "internal_error", "message": "Internal server error"}))
+    );
+}
+
+#[actix_web::test]
+async fn test_api_context() {
+    let result: Result<(), std::io::Error> = Err(std::io::Error::other("connection refused"));
+    let error: AnyApiError = result.api_context("cache_unavailable", StatusCode::SERVICE_UNAVAILABLE).unwrap_err().into();
+    let api_error = error.api_error();
+    assert_eq!(std::error::Error::source(&api_error).unwrap().to_string(), "connection refused");
+    assert_eq!(
+        render(error).await,
+        (503, serde_json::json!({"kind": "cache_unavailable", "message": "Service Unavailable"}))
+    );
+}
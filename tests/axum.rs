@@ -0,0 +1,83 @@
+#![cfg(feature = "axum")]
+
+use actix_error::*;
+use axum::body::Body;
+use axum::http::Request;
+use axum::routing::get;
+use axum::response::IntoResponse;
+use axum::Router;
+use tower::ServiceExt;
+
+#[derive(AsApiError, Debug)]
+pub enum BookError {
+    #[api_error(status = "NotFound", msg = "Book {0} not found")]
+    NotFound(u32),
+    #[api_error(status = "UnprocessableEntity", msg = "Invalid book")]
+    Invalid(serde_json::Value),
+}
+
+async fn missing_book() -> Result<&'static str, BookError> {
+    Err(BookError::NotFound(42))
+}
+
+async fn invalid_book() -> Result<&'static str, BookError> {
+    Err(BookError::Invalid(serde_json::json!({"field": "isbn"})))
+}
+
+async fn raw_error() -> Result<&'static str, ApiError> {
+    Err(ApiError::new(409, "conflict", "Already borrowed".to_string(), None))
+}
+
+/// Calls `uri` on the router, returning the status, the `ApiError` extension and the body.
+async fn call(uri: &str) -> (u16, Option<ApiError>, serde_json::Value) {
+    let app = Router::new()
+        .route("/missing", get(missing_book))
+        .route("/invalid", get(invalid_book))
+        .route("/raw", get(raw_error));
+    let res = app.oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap()).await.unwrap();
+    let status = res.status().as_u16();
+    let error = res.extensions().get::<ApiError>().cloned();
+    let bytes = axum::body::to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    (status, error, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn test_axum_into_response() {
+    let (status, error, body) = call("/missing").await;
+    assert_eq!(status, 404);
+    assert_eq!(error.unwrap().kind, "not_found");
+    assert_eq!(body, serde_json::json!({"kind": "not_found", "message": "Book 42 not found"}));
+
+    let (status, _, body) = call("/invalid").await;
+    assert_eq!(status, 422);
+    assert_eq!(body["details"], serde_json::json!({"field": "isbn"}));
+
+    let (status, _, body) = call("/raw").await;
+    assert_eq!(status, 409);
+    assert_eq!(body["message"], "Already borrowed");
+}
+
+/// Both frameworks must produce the same status and body for the same error.
+#[cfg(feature = "actix")]
+#[tokio::test]
+async fn test_axum_matches_actix() {
+    use actix_web::ResponseError;
+
+    for (uri, error) in [("/missing", BookError::NotFound(42)), ("/invalid", BookError::Invalid(serde_json::json!({"field": "isbn"})))] {
+        let actix_response = error.error_response();
+        let actix_status = actix_response.status().as_u16();
+        let actix_bytes = actix_web::body::to_bytes(actix_response.into_body()).await.unwrap();
+        let actix_body: serde_json::Value = serde_json::from_slice(&actix_bytes).unwrap();
+
+        let (status, _, body) = call(uri).await;
+        assert_eq!(status, actix_status);
+        assert_eq!(body, actix_body);
+    }
+}
+
+#[tokio::test]
+async fn test_axum_headers() {
+    let res = ApiError::too_many_requests().header("retry-after", "30").build().into_response();
+    assert_eq!(res.status().as_u16(), 429);
+    assert_eq!(res.headers().get("retry-after").unwrap(), "30");
+}
@@ -0,0 +1,72 @@
+use actix_error::*;
+
+#[test]
+fn test_builder_defaults() {
+    let error = ApiError::builder().build();
+    assert_eq!((error.code.as_u16(), error.kind.as_str(), error.message.as_str()), (500, "internal_server_error", "Internal Server Error"));
+
+    let error = ApiError::from(StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!((error.code.as_u16(), error.kind.as_str(), error.message.as_str()), (429, "too_many_requests", "Too Many Requests"));
+
+    let error = ApiError::from(StatusCode::IM_A_TEAPOT);
+    assert_eq!(error.kind, "i_m_a_teapot");
+
+    let error = ApiError::from(StatusCode::from_u16(599).unwrap());
+    assert_eq!((error.kind.as_str(), error.message.as_str()), ("error_599", "Error"));
+}
+
+#[test]
+fn test_builder_shorthands() {
+    let error: ApiError = ApiError::not_found("user").into();
+    assert_eq!((error.code.as_u16(), error.kind.as_str(), error.message.as_str()), (404, "not_found", "User not found"));
+
+    let error = ApiError::bad_request()
+        .kind("invalid_email")
+        .message("Email is invalid")
+        .detail("field", "email")
+        .detail("max_length", 254)
+        .build();
+    assert_eq!((error.code.as_u16(), error.kind.as_str(), error.message.as_str()), (400, "invalid_email", "Email is invalid"));
+    assert_eq!(error.details, Some(serde_json::json!({"field": "email", "max_length": 254})));
+
+    let error = ApiError::conflict().details(serde_json::json!(["a"])).detail("id", 3).build();
+    assert_eq!(error.details, Some(serde_json::json!({"id": 3})));
+
+    let error = ApiError::forbidden().id("E1001").build();
+    assert_eq!(error.id.as_deref(), Some("E1001"));
+
+    let error = ApiError::service_unavailable().source(std::io::Error::other("down")).build();
+    assert_eq!(std::error::Error::source(&error).unwrap().to_string(), "down");
+}
+
+#[cfg(feature = "actix")]
+#[actix_web::test]
+async fn test_builder_headers() {
+    use actix_web::ResponseError;
+
+    let error = ApiError::too_many_requests()
+        .header("retry-after", "30")
+        .header(http::header::WWW_AUTHENTICATE, "Bearer")
+        .build();
+    let res = error.error_response();
+    assert_eq!(res.status().as_u16(), 429);
+    assert_eq!(res.headers().get("retry-after").unwrap(), "30");
+    assert_eq!(res.headers().get("www-authenticate").unwrap(), "Bearer");
+    let bytes = actix_web::body::to_bytes(res.into_body()).await.unwrap();
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&bytes).unwrap(),
+        serde_json::json!({"kind": "too_many_requests", "message": "Too Many Requests"})
+    );
+}
+
+#[test]
+fn test_builder_invalid_header() {
+    let error = ApiError::too_many_requests().header("retry-after", "3\n0").header("bad name", "1").header("x-id", "7").build();
+    assert_eq!(error.headers.len(), 1);
+    assert_eq!(error.headers["x-id"], "7");
+
+    assert!(ApiError::too_many_requests().try_header("retry-after", "3\n0").is_err());
+    assert!(ApiError::too_many_requests().try_header("bad name", "1").is_err());
+    let error = ApiError::too_many_requests().try_header("retry-after", "30").unwrap().build();
+    assert_eq!(error.headers["retry-after"], "30");
+}
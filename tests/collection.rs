@@ -0,0 +1,123 @@
+#![cfg(feature = "actix")]
+
+use actix_error::*;
+use actix_web::{test, web, App, HttpResponse};
+use serde_json::json;
+
+#[derive(AsApiError, Debug)]
+pub enum ItemError {
+    #[api_error(status = "NotFound", msg = "Item {0} not found")]
+    NotFound(u32),
+    #[api_error(status = "Conflict", msg = "Item {0} is locked")]
+    Locked(u32),
+    #[api_error(code = 500, msg = "Storage failure")]
+    Storage,
+}
+
+fn batch() -> Vec<ItemError> {
+    vec![ItemError::Locked(1), ItemError::NotFound(2), ItemError::NotFound(3), ItemError::Storage]
+}
+
+async fn import() -> Result<HttpResponse, ApiErrors> {
+    Err(batch().into_iter().collect::<ApiErrors>().with_policy(StatusPolicy::Fixed(StatusCode::MULTI_STATUS)))
+}
+
+async fn import_json_api() -> Result<HttpResponse, ApiErrors> {
+    let mut errors = ApiErrors::new().with_format(ErrorsFormat::JsonApi).with_policy(StatusPolicy::Highest);
+    errors.push(&ItemError::NotFound(2));
+    errors.push(&ApiError::new(400, "bad_row", "Row 4 is malformed".to_string(), Some(json!({"row": 4}))));
+    Err(errors)
+}
+
+#[actix_web::test]
+async fn test_status_policies() {
+    let errors: ApiErrors = batch().into_iter().collect();
+    assert_eq!(errors.len(), 4);
+    assert_eq!(errors.status(), 409);
+    assert_eq!(errors.clone().with_policy(StatusPolicy::Highest).status(), 500);
+    assert_eq!(errors.clone().with_policy(StatusPolicy::MostCommon).status(), 404);
+    assert_eq!(errors.with_policy(StatusPolicy::Fixed(StatusCode::BAD_REQUEST)).status(), 400);
+
+    // Ties go to the earliest status.
+    let tied: ApiErrors = [ItemError::Locked(1), ItemError::NotFound(2)].into_iter().collect();
+    assert_eq!(tied.with_policy(StatusPolicy::MostCommon).status(), 409);
+
+    assert!(ApiErrors::new().is_empty());
+    assert_eq!(ApiErrors::new().status(), 500);
+}
+
+#[actix_web::test]
+async fn test_api_errors_rendered_by_middleware() {
+    let app = test::init_service(
+        App::new()
+            .app_data(ErrorConfig::new().status(true).path(true).envelope("error"))
+            .wrap(ErrorRenderer)
+            .wrap_fn(|req, srv| {
+                let fut = actix_web::dev::Service::call(srv, req);
+                async move {
+                    let res = fut.await?;
+                    assert!(ApiError::from_response_ext(&res).is_none());
+                    assert!(ApiErrors::from_response_ext(&res).is_some());
+                    Ok(res)
+                }
+            })
+            .route("/import", web::post().to(import))
+            .route("/import-json-api", web::post().to(import_json_api)),
+    )
+    .await;
+
+    let res = test::call_service(&app, test::TestRequest::post().uri("/import").to_request()).await;
+    assert_eq!(res.status(), 207);
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["error"]["errors"].as_array().map(Vec::len), Some(4));
+    assert_eq!(
+        body["error"]["errors"][0],
+        json!({"kind": "locked", "message": "Item 1 is locked", "status": 409, "path": "/import"})
+    );
+    assert_eq!(
+        body["error"]["errors"][3],
+        json!({"kind": "storage", "message": "Storage failure", "status": 500, "path": "/import"})
+    );
+
+    // JSON:API bodies keep their layout.
+    let res = test::call_service(&app, test::TestRequest::post().uri("/import-json-api").to_request()).await;
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["errors"][0]["code"], "not_found");
+    assert!(body.get("error").is_none());
+}
+
+#[actix_web::test]
+async fn test_api_errors_response() {
+    let app = test::init_service(
+        App::new()
+            .route("/import", web::post().to(import))
+            .route("/import-json-api", web::post().to(import_json_api)),
+    )
+    .await;
+
+    let res = test::call_service(&app, test::TestRequest::post().uri("/import").to_request()).await;
+    assert_eq!(res.status(), 207);
+    assert_eq!(res.headers().get("Content-Type").unwrap(), "application/json");
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(
+        body,
+        json!({"errors": [
+            {"kind": "locked", "message": "Item 1 is locked"},
+            {"kind": "not_found", "message": "Item 2 not found"},
+            {"kind": "not_found", "message": "Item 3 not found"},
+            {"kind": "storage", "message": "Storage failure"}
+        ]})
+    );
+
+    let res = test::call_service(&app, test::TestRequest::post().uri("/import-json-api").to_request()).await;
+    assert_eq!(res.status(), 404);
+    assert_eq!(res.headers().get("Content-Type").unwrap(), "application/vnd.api+json");
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(
+        body,
+        json!({"errors": [
+            {"status": "404", "code": "not_found", "title": "Not Found", "detail": "Item 2 not found"},
+            {"status": "400", "code": "bad_row", "title": "Bad Request", "detail": "Row 4 is malformed", "meta": {"row": 4}}
+        ]})
+    );
+}
@@ -0,0 +1,183 @@
+#![cfg(feature = "actix")]
+
+use actix_error::*;
+use actix_web::{test, web, App, HttpResponse};
+use serde_json::json;
+
+#[derive(AsApiError, Debug)]
+pub enum UserError {
+    #[api_error(status = "NotFound", msg = "User not found", msg_key = "user.not_found")]
+    NotFound,
+    #[api_error(status = "Conflict", msg = "Email already used")]
+    EmailTaken(serde_json::Value),
+}
+
+async fn missing_user() -> Result<HttpResponse, UserError> {
+    Err(UserError::NotFound)
+}
+
+async fn email_taken() -> Result<HttpResponse, UserError> {
+    Err(UserError::EmailTaken(json!({"existing_user": {"user_id": 3}})))
+}
+
+async fn raw_error() -> Result<HttpResponse, ApiError> {
+    Err(ApiError::new(429, "rateLimited", "Slow down".to_string(), Some(json!({"retryAfter": 30}))))
+}
+
+/// Checks that the timestamp is a recent RFC 3339 date, then removes it from the body.
+fn take_timestamp(body: &mut serde_json::Value) -> bool {
+    let Some(timestamp) = body.as_object_mut().unwrap().remove("timestamp") else {
+        return false;
+    };
+    let timestamp = chrono::DateTime::parse_from_rfc3339(timestamp.as_str().unwrap()).unwrap();
+    assert!((chrono::Utc::now() - timestamp.to_utc()).num_seconds().abs() < 5);
+    assert_eq!(timestamp.offset().local_minus_utc(), 0);
+    true
+}
+
+#[actix_web::test]
+async fn test_render_combinations() {
+    let error = ApiError::new(404, "user_not_found", "User not found".to_string(), Some(json!({"id": 42})));
+    for combination in 0..32 {
+        let enabled = |bit: u32| combination & (1 << bit) != 0;
+        let mut config = ErrorConfig::new().status(enabled(0)).timestamp(enabled(1)).path(enabled(2)).method(enabled(3));
+        if enabled(4) {
+            config = config.instance_id("api-eu-1");
+        }
+        assert_eq!(config.is_default(), combination == 0);
+
+        let mut expected = json!({"kind": "user_not_found", "message": "User not found", "details": {"id": 42}});
+        let fields = expected.as_object_mut().unwrap();
+        if enabled(0) {
+            fields.insert("status".to_string(), json!(404));
+        }
+        if enabled(2) {
+            fields.insert("path".to_string(), json!("/users/42"));
+        }
+        if enabled(3) {
+            fields.insert("method".to_string(), json!("DELETE"));
+        }
+        if enabled(4) {
+            fields.insert("instance".to_string(), json!("api-eu-1"));
+        }
+
+        let mut body = config.render(&error, "DELETE", "/users/42");
+        assert_eq!(take_timestamp(&mut body), enabled(1), "combination {:05b}", combination);
+        assert_eq!(body, expected, "combination {:05b}", combination);
+    }
+}
+
+#[actix_web::test]
+async fn test_error_renderer() {
+    let app = test::init_service(
+        App::new()
+            .app_data(ErrorConfig::new().status(true).method(true))
+            .wrap(ErrorRenderer)
+            .route("/users/{id}", web::get().to(missing_user))
+            .service(
+                web::scope("/admin")
+                    .app_data(ErrorConfig::new().path(true).timestamp(true))
+                    .route("/users/{id}", web::get().to(missing_user)),
+            ),
+    )
+    .await;
+
+    let res = test::call_service(&app, test::TestRequest::get().uri("/users/1").to_request()).await;
+    assert_eq!(res.status(), 404);
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body, json!({"kind": "not_found", "message": "User not found", "status": 404, "method": "GET"}));
+
+    // Scopes can register their own configuration.
+    let res = test::call_service(&app, test::TestRequest::get().uri("/admin/users/1").to_request()).await;
+    let mut body: serde_json::Value = test::read_body_json(res).await;
+    assert!(take_timestamp(&mut body));
+    assert_eq!(body, json!({"kind": "not_found", "message": "User not found", "path": "/admin/users/1"}));
+
+    // Without a configuration, the response is left untouched.
+    let app = test::init_service(App::new().wrap(ErrorRenderer).route("/users/{id}", web::get().to(missing_user))).await;
+    let res = test::call_service(&app, test::TestRequest::get().uri("/users/1").to_request()).await;
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body, json!({"kind": "not_found", "message": "User not found"}));
+}
+
+#[actix_web::test]
+async fn test_error_config_with_localize() {
+    let mut catalog = JsonCatalog::new();
+    catalog.insert("fr", "user.not_found", "Utilisateur introuvable");
+    let app = test::init_service(
+        App::new()
+            .app_data(ErrorConfig::new().status(true))
+            .wrap(Localize::new(catalog))
+            .route("/users/{id}", web::get().to(missing_user)),
+    )
+    .await;
+
+    let req = test::TestRequest::get().uri("/users/1").insert_header(("Accept-Language", "fr")).to_request();
+    let body: serde_json::Value = test::read_body_json(test::call_service(&app, req).await).await;
+    assert_eq!(body, json!({"kind": "not_found", "message": "Utilisateur introuvable", "status": 404}));
+}
+
+#[actix_web::test]
+async fn test_envelope_and_naming() {
+    let error = ApiError::new(404, "user_not_found", "User not found".to_string(), None);
+    assert_eq!(
+        ErrorConfig::new().envelope("error").render(&error, "GET", "/"),
+        json!({"error": {"kind": "user_not_found", "message": "User not found"}})
+    );
+    assert_eq!(
+        ErrorConfig::new().envelope("error").envelope_field("success", false).status(true).render(&error, "GET", "/"),
+        json!({"success": false, "error": {"kind": "user_not_found", "message": "User not found", "status": 404}})
+    );
+    // Envelope fields are ignored without an envelope key.
+    assert_eq!(
+        ErrorConfig::new().envelope_field("success", false).render(&error, "GET", "/"),
+        json!({"kind": "user_not_found", "message": "User not found"})
+    );
+
+    assert_eq!(Naming::CamelCase.apply("user_not_found"), "userNotFound");
+    assert_eq!(Naming::CamelCase.apply("_private_field"), "_privateField");
+    assert_eq!(Naming::CamelCase.apply("HTTPError"), "httpError");
+    assert_eq!(Naming::SnakeCase.apply("userNotFound"), "user_not_found");
+    // Consecutive capitals are an acronym, as for the `kind` generated by the derive macro.
+    assert_eq!(Naming::SnakeCase.apply("HTTPError"), "http_error");
+    assert_eq!(Naming::SnakeCase.apply("HttpError"), "http_error");
+    assert_eq!(Naming::SnakeCase.apply("parseHTTPResponse"), "parse_http_response");
+    assert_eq!(Naming::SnakeCase.apply("userID"), "user_id");
+    assert_eq!(Naming::SnakeCase.apply("_privateField"), "_private_field");
+    assert_eq!(Naming::Unchanged.apply("user_notFound"), "user_notFound");
+
+    let app = test::init_service(
+        App::new()
+            .app_data(
+                ErrorConfig::new()
+                    .envelope("error")
+                    .envelope_field("success", false)
+                    .envelope_field("request_id", "abc")
+                    .naming(Naming::CamelCase),
+            )
+            .wrap(ErrorRenderer)
+            .route("/email", web::get().to(email_taken))
+            .service(
+                web::scope("/v1")
+                    .app_data(ErrorConfig::new().naming(Naming::SnakeCase).naming_details(true))
+                    .route("/raw", web::get().to(raw_error)),
+            ),
+    )
+    .await;
+
+    let res = test::call_service(&app, test::TestRequest::get().uri("/email").to_request()).await;
+    assert_eq!(res.status(), 409);
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(
+        body,
+        json!({
+            "success": false,
+            "requestId": "abc",
+            "error": {"kind": "email_taken", "message": "Email already used", "details": {"existing_user": {"user_id": 3}}}
+        })
+    );
+
+    let res = test::call_service(&app, test::TestRequest::get().uri("/v1/raw").to_request()).await;
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body, json!({"kind": "rateLimited", "message": "Slow down", "details": {"retry_after": 30}}));
+}
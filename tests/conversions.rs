@@ -0,0 +1,99 @@
+use actix_error::*;
+
+#[derive(AsApiError, Debug, thiserror::Error)]
+pub enum ImportError {
+    #[api_error(group)]
+    #[error(transparent)]
+    Io(std::io::Error),
+    #[api_error(group)]
+    #[error(transparent)]
+    Json(serde_json::Error),
+    #[api_error(group)]
+    #[error(transparent)]
+    Count(std::num::ParseIntError),
+}
+
+#[test]
+fn test_io_error() {
+    use std::io::{Error, ErrorKind};
+
+    let cases = [
+        (ErrorKind::NotFound, 404, "not_found"),
+        (ErrorKind::PermissionDenied, 403, "permission_denied"),
+        (ErrorKind::AlreadyExists, 409, "already_exists"),
+        (ErrorKind::InvalidInput, 400, "invalid_input"),
+        (ErrorKind::TimedOut, 504, "timeout"),
+        (ErrorKind::ConnectionRefused, 503, "service_unavailable"),
+        (ErrorKind::Other, 500, "io_error"),
+    ];
+    for (kind, code, api_kind) in cases {
+        let error = Error::new(kind, "/etc/secret").as_api_error();
+        assert_eq!((error.code.as_u16(), error.kind.as_str()), (code, api_kind));
+        assert!(!error.message.contains("secret"));
+    }
+}
+
+#[test]
+fn test_group_variants() {
+    let error = ImportError::Io(std::io::Error::from(std::io::ErrorKind::NotFound)).as_api_error();
+    assert_eq!((error.code.as_u16(), error.kind.as_str()), (404, "not_found"));
+
+    let syntax = serde_json::from_str::<serde_json::Value>("{").unwrap_err();
+    let error = ImportError::Json(syntax).as_api_error();
+    assert_eq!((error.code.as_u16(), error.kind.as_str()), (400, "invalid_json"));
+    assert_eq!(error.message, "Invalid JSON");
+    assert_eq!(error.details, Some(serde_json::json!({"line": 1, "column": 1})));
+    // The serde message is only kept as the source.
+    assert_eq!(std::error::Error::source(&error).unwrap().to_string(), "EOF while parsing an object at line 1 column 1");
+
+    let data = serde_json::from_str::<Vec<u32>>("[1, true]").unwrap_err();
+    let error = ImportError::Json(data).as_api_error();
+    assert_eq!((error.code.as_u16(), error.kind.as_str()), (422, "invalid_json_data"));
+    assert_eq!(error.message, "Invalid JSON data");
+    assert_eq!(error.details, Some(serde_json::json!({"line": 1, "column": 8})));
+
+    // Errors without a position have no details.
+    let data = serde_json::from_value::<u32>(serde_json::json!("x")).unwrap_err();
+    let error = data.as_api_error();
+    assert_eq!((error.code.as_u16(), error.details), (422, None));
+
+    let error = ImportError::Count("12a".parse::<u32>().unwrap_err()).as_api_error();
+    assert_eq!((error.code.as_u16(), error.kind.as_str()), (400, "invalid_integer"));
+    assert_eq!(error.message, "Invalid integer: invalid digit found in string");
+}
+
+#[cfg(feature = "uuid")]
+#[test]
+fn test_uuid_error() {
+    let error = uuid::Uuid::parse_str("not-a-uuid").unwrap_err().as_api_error();
+    assert_eq!((error.code.as_u16(), error.kind.as_str()), (400, "invalid_uuid"));
+    assert!(error.message.starts_with("Invalid UUID: "));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn test_chrono_error() {
+    let error = chrono::NaiveDate::parse_from_str("2024-13-01", "%Y-%m-%d").unwrap_err().as_api_error();
+    assert_eq!((error.code.as_u16(), error.kind.as_str()), (400, "invalid_datetime"));
+    assert_eq!(error.message, "Invalid date or time: input is out of range");
+}
+
+#[cfg(feature = "actix")]
+#[test]
+fn test_actix_payload_errors() {
+    use actix_web::error::{JsonPayloadError, PayloadError};
+
+    assert_eq!(PayloadError::Overflow.as_api_error().code, 413);
+    assert_eq!(PayloadError::EncodingCorrupted.as_api_error().kind, "invalid_payload");
+
+    let cases = [
+        (JsonPayloadError::Overflow { limit: 10 }, 413, "payload_too_large"),
+        (JsonPayloadError::ContentType, 415, "unsupported_media_type"),
+        (JsonPayloadError::Deserialize(serde_json::from_str::<u32>("x").unwrap_err()), 400, "invalid_json"),
+        (JsonPayloadError::Payload(PayloadError::Overflow), 413, "payload_too_large"),
+    ];
+    for (error, code, kind) in cases {
+        let error = error.as_api_error();
+        assert_eq!((error.code.as_u16(), error.kind.as_str()), (code, kind));
+    }
+}
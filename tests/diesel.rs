@@ -0,0 +1,105 @@
+#![cfg(feature = "diesel")]
+
+use std::time::Duration;
+
+use actix_error::*;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool};
+use diesel::result::{DatabaseErrorInformation, DatabaseErrorKind};
+
+diesel::table! {
+    users (id) {
+        id -> Integer,
+        email -> Text,
+    }
+}
+
+/// Error information reporting its constraint name, as PostgreSQL does.
+struct NamedConstraint;
+
+impl DatabaseErrorInformation for NamedConstraint {
+    fn message(&self) -> &str {
+        "constraint violated"
+    }
+
+    fn details(&self) -> Option<&str> {
+        None
+    }
+
+    fn hint(&self) -> Option<&str> {
+        None
+    }
+
+    fn table_name(&self) -> Option<&str> {
+        None
+    }
+
+    fn column_name(&self) -> Option<&str> {
+        None
+    }
+
+    fn constraint_name(&self) -> Option<&str> {
+        Some("users_email_key")
+    }
+
+    fn statement_position(&self) -> Option<i32> {
+        None
+    }
+}
+
+#[test]
+fn test_diesel_sqlite_errors() {
+    let mut conn = SqliteConnection::establish(":memory:").unwrap();
+    diesel::sql_query("PRAGMA foreign_keys = ON").execute(&mut conn).unwrap();
+    diesel::sql_query("CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL UNIQUE)").execute(&mut conn).unwrap();
+    diesel::sql_query("CREATE TABLE posts (id INTEGER PRIMARY KEY, user_id INTEGER NOT NULL REFERENCES users(id))").execute(&mut conn).unwrap();
+    diesel::sql_query("INSERT INTO users (id, email) VALUES (1, 'ada@example.com')").execute(&mut conn).unwrap();
+
+    let error: ApiError = diesel::sql_query("INSERT INTO users (id, email) VALUES (2, 'ada@example.com')")
+        .execute(&mut conn)
+        .unwrap_err()
+        .into();
+    assert_eq!((error.code.as_u16(), error.kind.as_str(), &error.details), (409, "unique_violation", &None));
+    assert!(std::error::Error::source(&error).is_some());
+
+    let error: ApiError = diesel::sql_query("INSERT INTO posts (id, user_id) VALUES (1, 42)").execute(&mut conn).unwrap_err().into();
+    assert_eq!((error.code.as_u16(), error.kind.as_str()), (422, "foreign_key_violation"));
+
+    let error: ApiError = users::table.find(42).select(users::email).first::<String>(&mut conn).unwrap_err().into();
+    assert_eq!((error.code.as_u16(), error.kind.as_str()), (404, "not_found"));
+
+    let error: ApiError = diesel::sql_query("SELECT * FROM missing").execute(&mut conn).unwrap_err().into();
+    assert_eq!((error.code.as_u16(), error.kind.as_str(), error.message.as_str()), (500, "database_error", "Internal server error"));
+}
+
+#[test]
+fn test_diesel_pool_timeout() {
+    let pool = Pool::builder()
+        .max_size(1)
+        .connection_timeout(Duration::from_millis(50))
+        .build(ConnectionManager::<SqliteConnection>::new(":memory:"))
+        .unwrap();
+    let _connection = pool.get().unwrap();
+    let error: ApiError = pool.get().err().unwrap().into();
+    assert_eq!((error.code.as_u16(), error.kind.as_str()), (503, "database_unavailable"));
+}
+
+#[test]
+fn test_diesel_constraint_name() {
+    let unique = diesel::result::Error::DatabaseError(DatabaseErrorKind::UniqueViolation, Box::new(NamedConstraint));
+    let foreign_key = diesel::result::Error::DatabaseError(DatabaseErrorKind::ForeignKeyViolation, Box::new(NamedConstraint));
+    let error = unique.as_api_error();
+    assert_eq!((error.details.as_ref(), error.constraint.as_deref()), (None, Some("users_email_key")));
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        serde_json::json!({"kind": "unique_violation", "message": "Resource already exists"})
+    );
+    assert_eq!(
+        ErrorConfig::new().constraint_names(true).render(&foreign_key.as_api_error(), "POST", "/users"),
+        serde_json::json!({
+            "kind": "foreign_key_violation",
+            "message": "Referenced resource does not exist",
+            "details": {"constraint": "users_email_key"}
+        })
+    );
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "actix")]
+
+use actix_error::*;
+use actix_web::dev::Service;
+use actix_web::{test, web, App, HttpResponse};
+use std::error::Error;
+use std::sync::{Arc, Mutex};
+
+#[derive(AsApiError, Debug)]
+pub enum AccountError {
+    #[api_error(status = "Conflict", msg = "Account {0} already exists")]
+    AlreadyExists(String),
+    #[api_error(status = "ServiceUnavailable", msg = "Profile of {user} unreadable", msg_key = "account.profile")]
+    ProfileUnreadable {
+        user: String,
+        #[api_error(source)]
+        cause: Arc<std::io::Error>,
+    },
+}
+
+async fn conflict() -> Result<HttpResponse, AccountError> {
+    Err(AccountError::AlreadyExists("alice".to_string()))
+}
+
+async fn profile_unreadable() -> Result<HttpResponse, AccountError> {
+    let cause = Arc::new(std::io::Error::other("permission denied"));
+    Err(AccountError::ProfileUnreadable { user: "alice".to_string(), cause })
+}
+
+async fn io_failure() -> Result<HttpResponse, ApiError> {
+    let io = std::io::Error::other("disk on fire");
+    Err(ApiError::new(500, "storage", "Storage unavailable".to_string(), None).with_source(io))
+}
+
+#[actix_web::test]
+async fn test_api_error_in_response_extensions() {
+    let seen: Arc<Mutex<Vec<ApiError>>> = Arc::default();
+    let recorder = Arc::clone(&seen);
+
+    let app = test::init_service(
+        App::new()
+            .wrap_fn(move |req, srv| {
+                let recorder = Arc::clone(&recorder);
+                let fut = srv.call(req);
+                async move {
+                    let res = fut.await?;
+                    if let Some(error) = ApiError::from_response_ext(&res) {
+                        recorder.lock().unwrap().push(error);
+                    }
+                    Ok(res)
+                }
+            })
+            .route("/conflict", web::get().to(conflict))
+            .route("/io", web::get().to(io_failure))
+            .route("/profile", web::get().to(profile_unreadable))
+            .route("/ok", web::get().to(HttpResponse::Ok)),
+    )
+    .await;
+
+    let res = test::call_service(&app, test::TestRequest::get().uri("/conflict").to_request()).await;
+    assert_eq!(res.status(), 409);
+    let res = test::call_service(&app, test::TestRequest::get().uri("/io").to_request()).await;
+    assert_eq!(res.status(), 500);
+    let res = test::call_service(&app, test::TestRequest::get().uri("/profile").to_request()).await;
+    assert_eq!(res.status(), 503);
+    let res = test::call_service(&app, test::TestRequest::get().uri("/ok").to_request()).await;
+    assert_eq!(res.status(), 200);
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 3);
+
+    assert_eq!(seen[0].kind, "already_exists");
+    assert_eq!(seen[0].code, 409);
+    assert_eq!(seen[0].message, "Account alice already exists");
+    assert!(seen[0].source.is_none());
+
+    assert_eq!(seen[1].kind, "storage");
+    assert_eq!(seen[1].source().unwrap().to_string(), "disk on fire");
+
+    // A derived variant sets the field marked `#[api_error(source)]` as the source.
+    assert_eq!(seen[2].kind, "profile_unreadable");
+    assert_eq!(seen[2].message, "Profile of alice unreadable");
+    assert_eq!(seen[2].source().unwrap().to_string(), "permission denied");
+    // The source is not passed to the message catalog.
+    assert_eq!(seen[2].message_args, vec![("user".to_string(), "alice".to_string())]);
+}
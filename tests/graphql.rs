@@ -0,0 +1,50 @@
+#![cfg(feature = "async-graphql")]
+
+use actix_error::*;
+use async_graphql::{EmptyMutation, EmptySubscription, ErrorExtensions, Object, ResultExt, Schema};
+
+#[derive(AsApiError, Debug)]
+pub enum OrderError {
+    #[api_error(status = "NotFound", msg = "Order {0} not found")]
+    NotFound(u32),
+    #[api_error(status = "UnprocessableEntity", kind = "invalid_order", id = "O0422", msg = "Invalid order")]
+    Invalid(serde_json::Value),
+}
+
+struct Query;
+
+#[Object]
+impl Query {
+    async fn order(&self, id: u32) -> async_graphql::Result<String> {
+        Err(OrderError::NotFound(id)).extend()
+    }
+}
+
+#[test]
+fn test_graphql_extensions() {
+    let error = OrderError::Invalid(serde_json::json!({"field": "quantity"})).extend();
+    assert_eq!(error.message, "Invalid order");
+    assert_eq!(error.source.as_ref().unwrap().downcast_ref::<ApiError>().unwrap().kind, "invalid_order");
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        serde_json::json!({
+            "message": "Invalid order",
+            "extensions": {"code": "invalid_order", "id": "O0422", "status": 422, "details": {"field": "quantity"}}
+        })
+    );
+
+    let error = ApiError::new(409, "conflict", "Already paid".to_string(), None).extend();
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        serde_json::json!({"message": "Already paid", "extensions": {"code": "conflict", "status": 409}})
+    );
+}
+
+#[tokio::test]
+async fn test_graphql_response() {
+    let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+    let response = schema.execute("{ order(id: 3) }").await;
+    let errors = serde_json::to_value(&response.errors).unwrap();
+    assert_eq!(errors[0]["message"], "Order 3 not found");
+    assert_eq!(errors[0]["extensions"], serde_json::json!({"code": "not_found", "status": 404}));
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "tonic")]
+
+use actix_error::*;
+use tonic::Code;
+use tonic_types::StatusExt;
+
+#[derive(AsApiError, Debug)]
+pub enum AccountError {
+    #[api_error(status = "NotFound", msg = "Account {0} not found")]
+    NotFound(u32),
+    #[api_error(status = "Conflict", msg = "Account is locked", grpc = "FailedPrecondition")]
+    Locked,
+    #[api_error(status = "UnprocessableEntity", msg = "Invalid account", id = "A0422")]
+    Invalid(serde_json::Value),
+}
+
+#[test]
+fn test_grpc_code_mapping() {
+    assert_eq!(grpc_code_for_status(400), Code::InvalidArgument);
+    assert_eq!(grpc_code_for_status(401), Code::Unauthenticated);
+    assert_eq!(grpc_code_for_status(403), Code::PermissionDenied);
+    assert_eq!(grpc_code_for_status(404), Code::NotFound);
+    assert_eq!(grpc_code_for_status(409), Code::AlreadyExists);
+    assert_eq!(grpc_code_for_status(429), Code::ResourceExhausted);
+    assert_eq!(grpc_code_for_status(418), Code::InvalidArgument);
+    assert_eq!(grpc_code_for_status(503), Code::Unavailable);
+    assert_eq!(grpc_code_for_status(500), Code::Internal);
+    assert_eq!(grpc_code_for_status(302), Code::Unknown);
+}
+
+#[test]
+fn test_grpc_status_from_derived_error() {
+    let status = tonic::Status::from(AccountError::NotFound(7));
+    assert_eq!(status.code(), Code::NotFound);
+    assert_eq!(status.message(), "Account 7 not found");
+
+    let info = status.get_details_error_info().unwrap();
+    assert_eq!(info.reason, "NOT_FOUND");
+    assert_eq!(info.domain, "");
+    assert_eq!(info.metadata.get("kind").map(String::as_str), Some("not_found"));
+    assert!(!info.metadata.contains_key("details"));
+    assert!(!info.metadata.contains_key("id"));
+}
+
+#[test]
+fn test_grpc_code_override() {
+    assert_eq!(AccountError::Locked.as_api_error().grpc_code(), Code::FailedPrecondition);
+    let status: tonic::Status = AccountError::Locked.into();
+    assert_eq!(status.code(), Code::FailedPrecondition);
+
+    let error = ApiError::new(409, "conflict", "Conflict".to_string(), None).with_grpc_code(Code::Aborted as i32);
+    assert_eq!(tonic::Status::from(error).code(), Code::Aborted);
+}
+
+#[test]
+fn test_grpc_status_details() {
+    let error = AccountError::Invalid(serde_json::json!({"field": "iban"})).as_api_error();
+    let status = error.to_grpc_status("accounts.example.com");
+    assert_eq!(status.code(), Code::InvalidArgument);
+
+    let info = status.get_details_error_info().unwrap();
+    assert_eq!(info.domain, "accounts.example.com");
+    let details: serde_json::Value = serde_json::from_str(&info.metadata["details"]).unwrap();
+    assert_eq!(details, serde_json::json!({"field": "iban"}));
+    assert_eq!(info.metadata.get("id").map(String::as_str), Some("A0422"));
+}
@@ -0,0 +1,31 @@
+// The derive macro must not require the framework crates to be dependencies of the crate using it:
+// with unified features, a crate built with `default-features = false` may still get the framework
+// implementations. Local modules shadow the crate names to check that the generated code does not use them.
+#![allow(dead_code)]
+
+mod actix_web {}
+mod axum {}
+mod tonic {}
+mod async_graphql {}
+
+use actix_error::*;
+
+#[derive(AsApiError, Debug)]
+pub enum DomainError {
+    #[api_error(status = "NotFound", msg = "Order {0} not found")]
+    OrderNotFound(u32),
+}
+
+#[test]
+fn test_derive_without_framework_crates() {
+    assert_eq!(DomainError::OrderNotFound(3).as_api_error().code, 404);
+
+    #[cfg(feature = "actix")]
+    assert_eq!(::actix_web::ResponseError::status_code(&DomainError::OrderNotFound(3)), 404);
+    #[cfg(feature = "axum")]
+    assert_eq!(::axum::response::IntoResponse::into_response(DomainError::OrderNotFound(3)).status(), 404);
+    #[cfg(feature = "tonic")]
+    assert_eq!(::tonic::Status::from(DomainError::OrderNotFound(3)).code(), ::tonic::Code::NotFound);
+    #[cfg(feature = "async-graphql")]
+    assert_eq!(::async_graphql::ErrorExtensions::extend(&DomainError::OrderNotFound(3)).message, "Order 3 not found");
+}
@@ -0,0 +1,132 @@
+#![cfg(feature = "actix")]
+
+use actix_error::*;
+use actix_web::{test, web, App, HttpResponse};
+
+#[derive(AsApiError, Debug)]
+pub enum AuthError {
+    #[api_error(status = "BadRequest", msg = "Invalid password", msg_key = "auth.invalid_password")]
+    InvalidPassword,
+    #[api_error(status = "NotFound", msg = "User {name} not found", msg_key = "auth.user_not_found")]
+    UserNotFound { name: String },
+    #[api_error(code = 423, msg = "Account locked", msg_key = "auth.locked")]
+    Locked(u32, #[api_error(ignore)] Vec<u8>),
+}
+
+async fn invalid_password() -> Result<HttpResponse, AuthError> {
+    Err(AuthError::InvalidPassword)
+}
+
+async fn user_not_found() -> Result<HttpResponse, AuthError> {
+    Err(AuthError::UserNotFound { name: "ada".to_string() })
+}
+
+async fn locked() -> Result<HttpResponse, AuthError> {
+    Err(AuthError::Locked(3, Vec::new()))
+}
+
+async fn plain() -> Result<HttpResponse, ApiError> {
+    Err(ApiError::new(409, "conflict", "Conflict".to_string(), None))
+}
+
+async fn batch() -> Result<HttpResponse, ApiErrors> {
+    Err([AuthError::UserNotFound { name: "ada".to_string() }, AuthError::Locked(3, Vec::new())].into_iter().collect())
+}
+
+fn catalog() -> JsonCatalog {
+    let dir = std::env::temp_dir().join(format!("actix-error-i18n-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("fr.json"),
+        r#"{"auth": {"invalid_password": "Mot de passe invalide", "user_not_found": "Utilisateur {name} introuvable"}}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("en.json"),
+        r#"{"auth.invalid_password": "Wrong password", "auth.locked": "Locked after {0} attempts"}"#,
+    )
+    .unwrap();
+    let catalog = JsonCatalog::from_dir(&dir).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    catalog
+}
+
+/// Calls `uri` with an optional `Accept-Language` header, returning the status, `Content-Language` and body.
+/// Responses to errors with a message key must vary on `Accept-Language`, whether translated or not.
+macro_rules! call {
+    ($app:expr, $uri:expr, $lang:expr) => {{
+        let mut req = test::TestRequest::get().uri($uri);
+        if let Some(lang) = $lang {
+            req = req.insert_header(("Accept-Language", lang));
+        }
+        let res = test::call_service($app, req.to_request()).await;
+        assert_eq!(res.headers().get("Vary").map(|v| v.to_str().unwrap()), Some("accept-language"));
+        let status = res.status().as_u16();
+        let language = res.headers().get("Content-Language").map(|v| v.to_str().unwrap().to_string());
+        let body: serde_json::Value = test::read_body_json(res).await;
+        (status, language, body)
+    }};
+}
+
+#[actix_web::test]
+async fn test_message_key_arguments() {
+    let api_error = AuthError::UserNotFound { name: "ada".to_string() }.as_api_error();
+    assert_eq!(api_error.message, "User ada not found");
+    assert_eq!(api_error.message_key.as_deref(), Some("auth.user_not_found"));
+    assert_eq!(api_error.message_args, vec![("name".to_string(), "ada".to_string())]);
+
+    // Ignored fields are not passed to the catalog.
+    let api_error = AuthError::Locked(3, vec![1]).as_api_error();
+    assert_eq!(api_error.message_args, vec![("0".to_string(), "3".to_string())]);
+}
+
+#[actix_web::test]
+async fn test_localized_messages() {
+    let app = test::init_service(
+        App::new()
+            .wrap(Localize::new(catalog()).default_locale("en"))
+            .route("/password", web::get().to(invalid_password))
+            .route("/user", web::get().to(user_not_found))
+            .route("/locked", web::get().to(locked))
+            .route("/batch", web::get().to(batch))
+            .route("/plain", web::get().to(plain)),
+    )
+    .await;
+
+    let (status, language, body) = call!(&app, "/password", Some("fr-CA, en;q=0.5"));
+    assert_eq!(status, 400);
+    assert_eq!(language.as_deref(), Some("fr"));
+    assert_eq!(body["kind"], "invalid_password");
+    assert_eq!(body["message"], "Mot de passe invalide");
+
+    let (_, _, body) = call!(&app, "/password", Some("de, en;q=0.8, fr;q=0.5"));
+    assert_eq!(body["message"], "Wrong password");
+
+    let (status, language, body) = call!(&app, "/user", Some("fr"));
+    assert_eq!(status, 404);
+    assert_eq!(language.as_deref(), Some("fr"));
+    assert_eq!(body["message"], "Utilisateur ada introuvable");
+
+    // No French translation: falls back to the default locale.
+    let (status, language, body) = call!(&app, "/locked", Some("fr"));
+    assert_eq!(status, 423);
+    assert_eq!(language.as_deref(), Some("en"));
+    assert_eq!(body["message"], "Locked after 3 attempts");
+
+    // No translation at all: the default message is kept.
+    let (_, language, body) = call!(&app, "/user", None::<&str>);
+    assert_eq!(language, None);
+    assert_eq!(body["message"], "User ada not found");
+
+    // Each error of an `ApiErrors` response is translated; `Content-Language` is the first locale used.
+    let (status, language, body) = call!(&app, "/batch", Some("fr"));
+    assert_eq!(status, 404);
+    assert_eq!(language.as_deref(), Some("fr"));
+    assert_eq!(body["errors"][0]["message"], "Utilisateur ada introuvable");
+    assert_eq!(body["errors"][1]["message"], "Locked after 3 attempts");
+
+    // Errors without a message key do not depend on the language.
+    let res = test::call_service(&app, test::TestRequest::get().uri("/plain").to_request()).await;
+    assert_eq!(res.status(), 409);
+    assert!(res.headers().get("Vary").is_none());
+}
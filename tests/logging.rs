@@ -0,0 +1,50 @@
+#![cfg(all(feature = "log-errors", feature = "actix"))]
+
+use actix_error::*;
+use actix_web::ResponseError;
+use std::sync::Mutex;
+
+#[derive(AsApiError, Debug)]
+pub enum AuthError {
+    #[api_error(status = "BadRequest", msg = "Invalid password", log = "off")]
+    InvalidPassword,
+    #[api_error(status = "Conflict", msg = "Account {0} already exists", log = "warn")]
+    AlreadyExists(String),
+    #[api_error(code = 500, msg = "Directory unavailable")]
+    DirectoryDown,
+}
+
+static RECORDS: Mutex<Vec<(log::Level, String)>> = Mutex::new(Vec::new());
+
+struct CaptureLogger;
+
+impl log::Log for CaptureLogger {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+    fn log(&self, record: &log::Record) {
+        // Only keep the records emitted by the crate, not those forwarded from other facades.
+        if record.target() != "actix_error" {
+            return;
+        }
+        RECORDS.lock().unwrap().push((record.level(), record.args().to_string()));
+    }
+    fn flush(&self) {}
+}
+
+#[test]
+fn test_error_response_honors_log_level() {
+    log::set_logger(&CaptureLogger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    AuthError::InvalidPassword.error_response();
+    AuthError::AlreadyExists("bob".to_string()).error_response();
+    AuthError::DirectoryDown.error_response();
+
+    let records = RECORDS.lock().unwrap();
+    assert_eq!(records.len(), 2);
+    assert_eq!(records[0].0, log::Level::Warn);
+    assert_eq!(records[0].1, "409 already_exists: Account bob already exists");
+    assert_eq!(records[1].0, log::Level::Error);
+    assert_eq!(records[1].1, "500 directory_down: Directory unavailable");
+}
@@ -0,0 +1,82 @@
+#![cfg(all(feature = "metrics", feature = "actix"))]
+
+use actix_error::*;
+use actix_web::{test, web, App, HttpResponse};
+use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+use std::collections::HashMap;
+
+#[derive(AsApiError, Debug)]
+pub enum UserError {
+    #[api_error(status = "NotFound", msg = "User {0} not found")]
+    UserNotFound(u32),
+    #[api_error(status = "Conflict", msg = "Name taken")]
+    NameTaken,
+}
+
+async fn get_user(id: web::Path<u32>) -> Result<HttpResponse, UserError> {
+    Err(UserError::UserNotFound(id.into_inner()))
+}
+
+async fn create_user() -> Result<HttpResponse, UserError> {
+    Err(UserError::NameTaken)
+}
+
+async fn import_users() -> Result<HttpResponse, ApiErrors> {
+    Err([UserError::NameTaken, UserError::UserNotFound(5)].into_iter().collect())
+}
+
+/// Returns the counters as `(kind, status, route) -> value`.
+fn counters(recorder: &metrics_util::debugging::Snapshotter) -> HashMap<(String, String, String), u64> {
+    recorder
+        .snapshot()
+        .into_vec()
+        .into_iter()
+        .filter(|(key, ..)| key.key().name() == "api_errors_total")
+        .map(|(key, _, _, value)| {
+            let label = |name: &str| {
+                key.key().labels().find(|l| l.key() == name).map(|l| l.value().to_string()).unwrap()
+            };
+            let DebugValue::Counter(count) = value else { panic!("expected a counter") };
+            ((label("kind"), label("status"), label("route")), count)
+        })
+        .collect()
+}
+
+#[actix_web::test]
+async fn test_error_metrics_counter() {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    recorder.install().unwrap();
+
+    let app = test::init_service(
+        App::new()
+            .wrap(ErrorMetrics::new().max_series(2))
+            .route("/users/{id}", web::get().to(get_user))
+            .route("/users", web::post().to(create_user))
+            .route("/users", web::put().to(import_users))
+            .route("/teams", web::post().to(create_user))
+            .route("/health", web::get().to(HttpResponse::Ok)),
+    )
+    .await;
+
+    for uri in ["/users/1", "/users/2", "/health", "/missing"] {
+        test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+    }
+    let counts = counters(&snapshotter);
+    let key = |kind: &str, status: &str, route: &str| (kind.to_string(), status.to_string(), route.to_string());
+    assert_eq!(counts.len(), 1);
+    assert_eq!(counts[&key("user_not_found", "404", "/users/{id}")], 2);
+
+    test::call_service(&app, test::TestRequest::post().uri("/users").to_request()).await;
+    // The third distinct label set exceeds the limit and is collapsed.
+    test::call_service(&app, test::TestRequest::post().uri("/teams").to_request()).await;
+    let counts = counters(&snapshotter);
+    assert_eq!(counts[&key("name_taken", "409", "/users")], 1);
+    assert_eq!(counts[&key("other", "409", "other")], 1);
+
+    // Each error of an `ApiErrors` response is counted (snapshots report the increments since the previous one).
+    test::call_service(&app, test::TestRequest::put().uri("/users").to_request()).await;
+    let counts = counters(&snapshotter);
+    assert_eq!(counts[&key("name_taken", "409", "/users")], 1);
+    assert_eq!(counts[&key("other", "404", "other")], 1);
+}
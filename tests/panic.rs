@@ -0,0 +1,66 @@
+#![cfg(feature = "actix")]
+
+use actix_error::*;
+use actix_web::{test, web, App, HttpResponse};
+
+async fn panicking_handler() -> HttpResponse {
+    panic!("boom: secret internal state");
+}
+
+async fn ok_handler() -> HttpResponse {
+    HttpResponse::Ok().body("fine")
+}
+
+/// `CatchPanic` hands the error back to actix-web, which renders it when writing the response,
+/// so the tests render it the same way.
+async fn rendered_body(err: actix_web::Error) -> (u16, serde_json::Value) {
+    let res = err.error_response();
+    let status = res.status().as_u16();
+    let bytes = actix_web::body::to_bytes(res.into_body()).await.unwrap();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[actix_web::test]
+async fn test_catch_panic_default_error() {
+    let app = test::init_service(
+        App::new()
+            .wrap(CatchPanic::default())
+            .route("/panic", web::get().to(panicking_handler))
+            .route("/ok", web::get().to(ok_handler)),
+    )
+    .await;
+
+    let err = test::try_call_service(&app, test::TestRequest::get().uri("/panic").to_request())
+        .await
+        .expect_err("the panic should be turned into an error");
+    assert_eq!(err.as_error::<ApiError>().unwrap().kind, "internal_panic");
+    let (status, body) = rendered_body(err).await;
+    assert_eq!(status, 500);
+    assert_eq!(body["kind"], "internal_panic");
+    assert_eq!(body["message"], "Internal server error");
+    assert!(!body.to_string().contains("secret"));
+
+    // The service keeps working after a panic.
+    let res = test::call_service(&app, test::TestRequest::get().uri("/ok").to_request()).await;
+    assert_eq!(res.status(), 200);
+    assert_eq!(test::read_body(res).await, "fine");
+}
+
+#[actix_web::test]
+async fn test_catch_panic_custom_error() {
+    let error = ApiError::new(503, "unavailable", "Try again later".to_string(), None);
+    let app = test::init_service(
+        App::new()
+            .wrap(CatchPanic::new(error))
+            .route("/panic", web::get().to(panicking_handler)),
+    )
+    .await;
+
+    let err = test::try_call_service(&app, test::TestRequest::get().uri("/panic").to_request())
+        .await
+        .expect_err("the panic should be turned into an error");
+    let (status, body) = rendered_body(err).await;
+    assert_eq!(status, 503);
+    assert_eq!(body["kind"], "unavailable");
+    assert_eq!(body["message"], "Try again later");
+}
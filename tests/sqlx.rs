@@ -0,0 +1,103 @@
+#![cfg(feature = "sqlx")]
+
+use std::time::Duration;
+
+use actix_error::*;
+use sqlx::error::{DatabaseError, ErrorKind};
+use sqlx::sqlite::SqlitePoolOptions;
+
+/// A database error reporting its constraint name, as PostgreSQL does.
+#[derive(Debug)]
+struct NamedConstraintError(ErrorKind);
+
+impl std::fmt::Display for NamedConstraintError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "constraint violated")
+    }
+}
+
+impl std::error::Error for NamedConstraintError {}
+
+impl DatabaseError for NamedConstraintError {
+    fn message(&self) -> &str {
+        "constraint violated"
+    }
+
+    fn as_error(&self) -> &(dyn std::error::Error + Send + Sync + 'static) {
+        self
+    }
+
+    fn as_error_mut(&mut self) -> &mut (dyn std::error::Error + Send + Sync + 'static) {
+        self
+    }
+
+    fn into_error(self: Box<Self>) -> Box<dyn std::error::Error + Send + Sync + 'static> {
+        self
+    }
+
+    fn constraint(&self) -> Option<&str> {
+        Some("users_email_key")
+    }
+
+    fn kind(&self) -> ErrorKind {
+        match self.0 {
+            ErrorKind::UniqueViolation => ErrorKind::UniqueViolation,
+            _ => ErrorKind::ForeignKeyViolation,
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_sqlx_sqlite_errors() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .acquire_timeout(Duration::from_millis(50))
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    sqlx::query("CREATE TABLE users (id INTEGER PRIMARY KEY, email TEXT NOT NULL UNIQUE)").execute(&pool).await.unwrap();
+    sqlx::query("CREATE TABLE posts (id INTEGER PRIMARY KEY, user_id INTEGER NOT NULL REFERENCES users(id))").execute(&pool).await.unwrap();
+    sqlx::query("INSERT INTO users (id, email) VALUES (1, 'ada@example.com')").execute(&pool).await.unwrap();
+
+    let error: ApiError = sqlx::query("INSERT INTO users (id, email) VALUES (2, 'ada@example.com')")
+        .execute(&pool)
+        .await
+        .unwrap_err()
+        .into();
+    assert_eq!((error.code.as_u16(), error.kind.as_str(), &error.details), (409, "unique_violation", &None));
+    assert!(std::error::Error::source(&error).is_some());
+
+    let error: ApiError = sqlx::query("INSERT INTO posts (id, user_id) VALUES (1, 42)").execute(&pool).await.unwrap_err().into();
+    assert_eq!((error.code.as_u16(), error.kind.as_str()), (422, "foreign_key_violation"));
+
+    let error: ApiError = sqlx::query_scalar::<_, String>("SELECT email FROM users WHERE id = 42").fetch_one(&pool).await.unwrap_err().into();
+    assert_eq!((error.code.as_u16(), error.kind.as_str()), (404, "not_found"));
+
+    let connection = pool.acquire().await.unwrap();
+    let error: ApiError = pool.acquire().await.unwrap_err().into();
+    assert_eq!((error.code.as_u16(), error.kind.as_str()), (503, "database_unavailable"));
+    drop(connection);
+
+    let error: ApiError = sqlx::query("SELECT * FROM missing").execute(&pool).await.unwrap_err().into();
+    assert_eq!((error.code.as_u16(), error.kind.as_str(), error.message.as_str()), (500, "database_error", "Internal server error"));
+}
+
+#[test]
+fn test_sqlx_constraint_name() {
+    let unique = sqlx::Error::Database(Box::new(NamedConstraintError(ErrorKind::UniqueViolation)));
+    let foreign_key = sqlx::Error::Database(Box::new(NamedConstraintError(ErrorKind::ForeignKeyViolation)));
+    let error = unique.as_api_error();
+    assert_eq!((error.details.as_ref(), error.constraint.as_deref()), (None, Some("users_email_key")));
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        serde_json::json!({"kind": "unique_violation", "message": "Resource already exists"})
+    );
+    assert_eq!(
+        ErrorConfig::new().constraint_names(true).render(&foreign_key.as_api_error(), "POST", "/users"),
+        serde_json::json!({
+            "kind": "foreign_key_violation",
+            "message": "Referenced resource does not exist",
+            "details": {"constraint": "users_email_key"}
+        })
+    );
+}
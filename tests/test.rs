@@ -151,3 +151,277 @@ async fn test_thiserror_display_integration() {
     assert_eq!(api_error3.kind, "simple_error");
     assert_eq!(api_error3.message, "Just a simple error from thiserror"); // From thiserror's Display
 }
+
+#[derive(AsApiError, Debug)]
+pub enum LeveledError {
+    #[api_error(status = "Conflict", msg = "Duplicate booking", level = "error")]
+    DuplicateBooking,
+    #[api_error(status = "NotFound", msg = "Room not found")]
+    RoomNotFound,
+    #[api_error(status = "BadRequest", msg = "Invalid password", log = "off")]
+    InvalidPassword,
+    #[api_error(status = "Conflict", msg = "Already member", log = "warn")]
+    AlreadyMember,
+    #[api_error(group)]
+    Grp(GrpError),
+}
+
+#[test]
+fn test_error_level() {
+    assert_eq!(LeveledError::DuplicateBooking.as_api_error().level(), Level::Error);
+    assert_eq!(LeveledError::RoomNotFound.as_api_error().level(), Level::Info);
+    assert_eq!(LeveledError::Grp(GrpError::FirewallFail).as_api_error().level(), Level::Error);
+    assert_eq!(LeveledError::InvalidPassword.log_level(), Level::Off);
+    assert_eq!(LeveledError::AlreadyMember.log_level(), Level::Warn);
+    assert_eq!(LeveledError::RoomNotFound.log_level(), Level::Info);
+
+    let api_error = ApiError::new(404, "missing", "Missing".to_string(), None);
+    assert_eq!(api_error.level, None);
+    assert_eq!(api_error.with_level(Level::Warn).level(), Level::Warn);
+    assert_eq!("warn".parse::<Level>(), Ok(Level::Warn));
+    assert!("loud".parse::<Level>().is_err());
+}
+
+#[test]
+fn test_status_code() {
+    let error = ApiError::new(404, "missing", "Missing".to_string(), None);
+    assert_eq!(error.code, StatusCode::NOT_FOUND);
+    assert_eq!(error.code, 404);
+
+    assert!(ApiError::try_new(42, "invalid", "Invalid".to_string(), None).is_err());
+    let error = ApiError::try_new(418, "teapot", "Teapot".to_string(), None).unwrap();
+    assert_eq!(error.code.as_u16(), 418);
+
+    let error = ApiError::with_status(StatusCode::GONE, "gone", "Gone".to_string(), None);
+    assert_eq!(serde_json::to_value(&error).unwrap(), serde_json::json!({"kind": "gone", "message": "Gone"}));
+    assert_eq!(
+        serde_json::to_value(error.with_status_in_body()).unwrap(),
+        serde_json::json!({"kind": "gone", "message": "Gone", "status": 410})
+    );
+}
+
+#[test]
+#[should_panic(expected = "invalid status code 42")]
+fn test_invalid_status_code() {
+    ApiError::new(42, "invalid", "Invalid".to_string(), None);
+}
+
+#[derive(AsApiError, Debug)]
+#[api_error(require_ids)]
+pub enum PaymentError {
+    #[api_error(id = "E1042", code = 402, kind = "card_declined", msg = "Card declined")]
+    CardDeclined,
+    #[api_error(id = "E1043", code = 409, msg = "Payment {0} already captured")]
+    AlreadyCaptured(u32),
+    #[api_error(group)]
+    Other(Box<ApiError>),
+}
+
+#[test]
+fn test_error_id() {
+    let error = PaymentError::CardDeclined.as_api_error();
+    assert_eq!(error.id.as_deref(), Some("E1042"));
+    assert_eq!(
+        serde_json::to_value(&error).unwrap(),
+        serde_json::json!({"kind": "card_declined", "id": "E1042", "message": "Card declined"})
+    );
+    assert_eq!(PaymentError::AlreadyCaptured(7).as_api_error().id.as_deref(), Some("E1043"));
+
+    let error = PaymentError::Other(Box::new(ApiError::new(500, "internal", "Internal".to_string(), None))).as_api_error();
+    assert_eq!(error.id, None);
+    assert_eq!(serde_json::to_value(&error).unwrap(), serde_json::json!({"kind": "internal", "message": "Internal"}));
+
+    let error = ApiError::new(404, "missing", "Missing".to_string(), None).with_id("E2001");
+    assert_eq!(error.id.as_deref(), Some("E2001"));
+}
+
+#[derive(AsApiError, Debug)]
+pub enum SessionError {
+    #[api_error(code = 401, kind = "session_expired", msg = "Session expired")]
+    Expired,
+    /// Kept for clients that still send the old token format.
+    #[api_error(code = 401, kind = "session_expired", msg = "Session expired", allow_duplicate_kind)]
+    LegacyTokenExpired,
+}
+
+#[test]
+fn test_allow_duplicate_kind() {
+    assert_eq!(SessionError::Expired.as_api_error().kind, "session_expired");
+    assert_eq!(SessionError::LegacyTokenExpired.as_api_error().kind, "session_expired");
+}
+
+#[derive(AsApiError, Debug)]
+#[api_error(msg_from_doc)]
+pub enum DocError {
+    /// Invalid password
+    #[api_error(status = "BadRequest", id = "E2001")]
+    InvalidPassword,
+    /// User {0} not found
+    ///
+    /// The user may have been deleted.
+    #[api_error(status = "NotFound")]
+    UserNotFound(u32),
+    /// Name is invalid
+    #[api_error(code = 422, msg = "Name {name} is too long")]
+    NameTooLong { name: String },
+    #[api_error(code = 500)]
+    Undocumented,
+    /// {0}
+    #[api_error(group)]
+    Other(GrpError),
+}
+
+#[derive(AsApiError, Debug)]
+pub enum VariantDocError {
+    /// Quota of {limit} requests exceeded
+    #[api_error(code = 429, msg_from_doc)]
+    QuotaExceeded { limit: u32 },
+    /// Not used as the message
+    #[api_error(code = 503, msg = "Maintenance")]
+    Maintenance,
+}
+
+#[test]
+fn test_msg_from_doc() {
+    assert_eq!(DocError::InvalidPassword.as_api_error().message, "Invalid password");
+    assert_eq!(DocError::UserNotFound(7).as_api_error().message, "User 7 not found");
+    assert_eq!(DocError::UserNotFound(7).to_string(), "User 7 not found");
+    assert_eq!(DocError::NameTooLong { name: "Bob".to_string() }.as_api_error().message, "Name Bob is too long");
+    assert_eq!(DocError::Undocumented.as_api_error().message, "Undocumented");
+    assert_eq!(VariantDocError::QuotaExceeded { limit: 100 }.as_api_error().message, "Quota of 100 requests exceeded");
+    assert_eq!(VariantDocError::Maintenance.as_api_error().message, "Maintenance");
+}
+
+#[test]
+fn test_error_catalog() {
+    let catalog = DocError::error_catalog();
+    assert_eq!(catalog.len(), 4);
+    assert_eq!(
+        catalog[0],
+        ErrorDescriptor { kind: "invalid_password", status: CatalogStatus::Fixed(400), id: Some("E2001"), description: Some("Invalid password") }
+    );
+    assert_eq!(catalog[1].description, Some("User {0} not found\n\nThe user may have been deleted."));
+    assert_eq!(catalog[3], ErrorDescriptor { kind: "undocumented", status: CatalogStatus::Fixed(500), id: None, description: None });
+    assert_eq!(
+        serde_json::to_value(catalog[0]).unwrap(),
+        serde_json::json!({"kind": "invalid_password", "status": 400, "id": "E2001", "description": "Invalid password"})
+    );
+}
+
+fn quota_message(limit: &u32, used: &u32) -> String {
+    match limit - used {
+        1 => "1 request left".to_string(),
+        left => format!("{} requests left", left),
+    }
+}
+
+fn quota_details(limit: &u32, used: &u32) -> Option<serde_json::Value> {
+    Some(serde_json::json!({"limit": limit, "used": used}))
+}
+
+mod messages {
+    pub fn retry_message(retry_after: &std::time::Duration) -> String {
+        format!("Retry in {} seconds", retry_after.as_secs())
+    }
+}
+
+#[derive(AsApiError, Debug)]
+pub enum QuotaError {
+    #[api_error(code = 429, msg_fn = quota_message, details_fn = quota_details)]
+    QuotaAlmostReached { limit: u32, used: u32 },
+    #[api_error(code = 503, msg_fn = messages::retry_message)]
+    Unavailable(std::time::Duration),
+    #[api_error(code = 400, msg = "Invalid quota", details_fn = quota_details)]
+    Invalid(u32, u32),
+}
+
+#[test]
+fn test_msg_fn_and_details_fn() {
+    let error = QuotaError::QuotaAlmostReached { limit: 10, used: 9 }.as_api_error();
+    assert_eq!(error.message, "1 request left");
+    assert_eq!(error.details, Some(serde_json::json!({"limit": 10, "used": 9})));
+    assert_eq!(QuotaError::QuotaAlmostReached { limit: 10, used: 7 }.to_string(), "3 requests left");
+
+    let error = QuotaError::Unavailable(std::time::Duration::from_secs(30)).as_api_error();
+    assert_eq!((error.message.as_str(), error.details), ("Retry in 30 seconds", None));
+
+    let error = QuotaError::Invalid(1, 2).as_api_error();
+    assert_eq!(error.message, "Invalid quota");
+    assert_eq!(error.details, Some(serde_json::json!({"limit": 1, "used": 2})));
+}
+
+fn timeout_code(retryable: &bool) -> u16 {
+    if *retryable { 503 } else { 504 }
+}
+
+#[derive(AsApiError, Debug)]
+pub enum UpstreamError {
+    #[api_error(code = status, status = "BadGateway", msg = "Upstream failed with {status}")]
+    UpstreamFailed { status: u16 },
+    #[api_error(code = status, msg = "Upstream failed")]
+    UpstreamFailedWide { status: i64 },
+    #[api_error(code_fn = timeout_code, msg = "Upstream timed out")]
+    TimedOut(bool),
+}
+
+#[test]
+fn test_dynamic_status() {
+    let error = UpstreamError::UpstreamFailed { status: 404 }.as_api_error();
+    assert_eq!((error.code.as_u16(), error.message.as_str()), (404, "Upstream failed with 404"));
+    assert_eq!(UpstreamError::UpstreamFailed { status: 42 }.as_api_error().code.as_u16(), 502);
+    assert_eq!(UpstreamError::UpstreamFailed { status: 503 }.log_level(), Level::Error);
+
+    assert_eq!(UpstreamError::UpstreamFailedWide { status: 429 }.as_api_error().code.as_u16(), 429);
+    assert_eq!(UpstreamError::UpstreamFailedWide { status: 70_000 }.as_api_error().code.as_u16(), 500);
+
+    assert_eq!(UpstreamError::TimedOut(true).as_api_error().code.as_u16(), 503);
+    assert_eq!(UpstreamError::TimedOut(false).as_api_error().code.as_u16(), 504);
+
+    let catalog = UpstreamError::error_catalog();
+    assert!(catalog.iter().all(|descriptor| descriptor.status == CatalogStatus::Dynamic));
+    assert_eq!(serde_json::to_value(catalog[0]).unwrap()["status"], "dynamic");
+}
+
+// Every variant is documented, so `no_display` is needed to combine `msg_from_doc` with `thiserror`.
+#[derive(AsApiError, Debug, thiserror::Error)]
+#[api_error(msg_from_doc, no_display)]
+pub enum DocThiserrorError {
+    /// Item {0} not found
+    #[api_error(status = "NotFound")]
+    #[error("Item {0} is missing")]
+    ItemNotFound(String),
+    /// Invalid credentials for {username}
+    #[api_error(status = "Unauthorized")]
+    #[error("Authentication failed for {username}")]
+    AuthFailure { username: String },
+}
+
+#[test]
+fn test_msg_from_doc_with_thiserror() {
+    let error = DocThiserrorError::ItemNotFound("42".to_string());
+    assert_eq!(error.as_api_error().message, "Item 42 not found");
+    assert_eq!(error.to_string(), "Item 42 is missing");
+    let error = DocThiserrorError::AuthFailure { username: "bob".to_string() };
+    assert_eq!(error.as_api_error().message, "Invalid credentials for bob");
+    assert_eq!(error.to_string(), "Authentication failed for bob");
+}
+
+// Without `thiserror`, an enum whose messages all come from doc comments gets a generated `Display`.
+#[derive(AsApiError, Debug)]
+#[api_error(msg_from_doc)]
+pub enum DocOnlyError {
+    /// Item {0} not found
+    #[api_error(status = "NotFound")]
+    ItemNotFound(String),
+    /// The service is in maintenance
+    #[api_error(status = "ServiceUnavailable")]
+    Maintenance,
+}
+
+#[test]
+fn test_msg_from_doc_generates_display() {
+    let error = DocOnlyError::ItemNotFound("42".to_string());
+    assert_eq!(error.as_api_error().message, "Item 42 not found");
+    assert_eq!(error.to_string(), "Item 42 not found");
+    assert_eq!(DocOnlyError::Maintenance.to_string(), "The service is in maintenance");
+}
@@ -0,0 +1,97 @@
+#![cfg(all(feature = "tracing", feature = "actix"))]
+
+use actix_error::*;
+use actix_web::ResponseError;
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+#[derive(AsApiError, Debug)]
+pub enum LoginError {
+    #[api_error(status = "Conflict", msg = "Session already open", level = "warn")]
+    SessionOpen,
+    #[api_error(status = "BadRequest", msg = "Invalid password")]
+    InvalidPassword,
+    #[api_error(code = 503, msg = "Directory unavailable")]
+    DirectoryDown,
+}
+
+#[derive(Debug, Default)]
+struct CapturedEvent {
+    level: Option<tracing::Level>,
+    fields: Vec<(String, String)>,
+}
+
+impl CapturedEvent {
+    fn field(&self, name: &str) -> Option<&str> {
+        self.fields.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str())
+    }
+}
+
+impl Visit for CapturedEvent {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.fields.push((field.name().to_string(), format!("{:?}", value)));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.fields.push((field.name().to_string(), value.to_string()));
+    }
+}
+
+/// A minimal subscriber keeping every event it receives.
+#[derive(Clone, Default)]
+struct Capture(Arc<Mutex<Vec<CapturedEvent>>>);
+
+impl Subscriber for Capture {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+    fn new_span(&self, _: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+    fn event(&self, event: &Event<'_>) {
+        let mut captured = CapturedEvent {
+            level: Some(*event.metadata().level()),
+            ..Default::default()
+        };
+        event.record(&mut captured);
+        self.0.lock().unwrap().push(captured);
+    }
+    fn enter(&self, _: &Id) {}
+    fn exit(&self, _: &Id) {}
+}
+
+#[test]
+fn test_error_response_records_event() {
+    let capture = Capture::default();
+    tracing::subscriber::with_default(capture.clone(), || {
+        LoginError::SessionOpen.error_response();
+        LoginError::InvalidPassword.error_response();
+        LoginError::DirectoryDown.error_response();
+
+        let cause = std::io::Error::other("connection reset");
+        ApiError::new(502, "upstream", "Upstream failed".to_string(), Some(serde_json::json!({"host": "ldap"})))
+            .with_source(cause)
+            .error_response();
+    });
+
+    let events = capture.0.lock().unwrap();
+    assert_eq!(events.len(), 4);
+
+    assert_eq!(events[0].level, Some(tracing::Level::WARN));
+    assert_eq!(events[0].field("error.kind"), Some("session_open"));
+    assert_eq!(events[0].field("http.status_code"), Some("409"));
+    assert_eq!(events[0].field("error.message"), Some("Session already open"));
+    assert_eq!(events[0].field("error.details"), None);
+
+    // Defaults by status class.
+    assert_eq!(events[1].level, Some(tracing::Level::INFO));
+    assert_eq!(events[2].level, Some(tracing::Level::ERROR));
+
+    assert_eq!(events[3].field("error.kind"), Some("upstream"));
+    assert_eq!(events[3].field("error.details"), Some(r#"{"host":"ldap"}"#));
+    assert_eq!(events[3].field("error.source"), Some("connection reset"));
+}
@@ -0,0 +1,6 @@
+// Compile errors of the derive macro. Run with `TRYBUILD=overwrite` to update the `.stderr` snapshots.
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
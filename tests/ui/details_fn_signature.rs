@@ -0,0 +1,13 @@
+use actix_error::*;
+
+fn quota_details(limit: &u32) -> serde_json::Value {
+    serde_json::json!({ "limit": limit })
+}
+
+#[derive(AsApiError, Debug)]
+pub enum QuotaError {
+    #[api_error(code = 429, msg = "Quota exceeded", details_fn = quota_details)]
+    Exceeded { limit: u32 },
+}
+
+fn main() {}
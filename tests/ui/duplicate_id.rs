@@ -0,0 +1,11 @@
+use actix_error::*;
+
+#[derive(AsApiError, Debug)]
+pub enum PaymentError {
+    #[api_error(id = "E1042", code = 402, msg = "Card declined")]
+    CardDeclined,
+    #[api_error(id = "E1042", code = 409, msg = "Payment already captured")]
+    AlreadyCaptured,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use actix_error::*;
+
+#[derive(AsApiError, Debug)]
+pub enum SessionError {
+    #[api_error(code = 401, kind = "session_expired", msg = "Session expired")]
+    Expired,
+    #[api_error(code = 401, kind = "session_expired", msg = "Token expired")]
+    TokenExpired,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use actix_error::*;
+
+#[derive(AsApiError, Debug)]
+pub enum UpstreamError {
+    #[api_error(code = 502, msg = "Upstream failed")]
+    HTTPError,
+    #[api_error(code = 502, msg = "Upstream failed")]
+    HttpError,
+}
+
+fn main() {}
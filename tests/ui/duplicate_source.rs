@@ -0,0 +1,15 @@
+use actix_error::*;
+use std::sync::Arc;
+
+#[derive(AsApiError, Debug)]
+pub enum SyncError {
+    #[api_error(status = "BadGateway", msg = "Sync failed")]
+    Failed {
+        #[api_error(source)]
+        read: Arc<std::io::Error>,
+        #[api_error(source)]
+        write: Arc<std::io::Error>,
+    },
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use actix_error::*;
+
+#[derive(AsApiError, Debug)]
+#[api_error(require_ids)]
+pub enum PaymentError {
+    #[api_error(id = "E1042", code = 402, msg = "Card declined")]
+    CardDeclined,
+    #[api_error(code = 409, msg = "Payment already captured")]
+    AlreadyCaptured,
+    #[api_error(group)]
+    Other(Box<ApiError>),
+}
+
+fn main() {}
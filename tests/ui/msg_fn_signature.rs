@@ -0,0 +1,19 @@
+use actix_error::*;
+
+fn retry_message(retry_after: std::time::Duration) -> String {
+    format!("Retry in {} seconds", retry_after.as_secs())
+}
+
+fn quota_message(limit: &u32) -> &'static str {
+    if *limit == 0 { "No quota" } else { "Quota exceeded" }
+}
+
+#[derive(AsApiError, Debug)]
+pub enum QuotaError {
+    #[api_error(code = 503, msg_fn = retry_message)]
+    Unavailable(std::time::Duration),
+    #[api_error(code = 429, msg_fn = quota_message)]
+    Exceeded { limit: u32 },
+}
+
+fn main() {}
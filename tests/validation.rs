@@ -0,0 +1,78 @@
+#![cfg(feature = "actix")]
+
+use actix_error::*;
+use actix_web::{test, web, App, HttpResponse};
+use serde_json::json;
+
+#[derive(AsApiError, Debug)]
+pub enum SignupError {
+    #[api_error(group)]
+    Invalid(ValidationErrors),
+    #[api_error(status = "Conflict", msg = "Email already used")]
+    EmailTaken,
+}
+
+fn validate_signup(email: &str, password: &str, zip: &str) -> Result<(), ValidationErrors> {
+    let mut address = ValidationErrors::new();
+    address.check(zip.len() == 5, "zip", || ValidationIssue::new("invalid_zip", "Invalid zip code"));
+
+    let mut errors = ValidationErrors::new();
+    errors
+        .check(email.contains('@'), "email", || ValidationIssue::new("invalid_email", "Email is invalid"))
+        .check(password.len() >= 8, "password", || {
+            ValidationIssue::new("too_short", "Password is too short").param("min", 8)
+        })
+        .check(password.chars().any(|c| c.is_ascii_digit()), "password", || {
+            ValidationIssue::new("missing_digit", "Password needs a digit")
+        })
+        .merge("address", address);
+    errors.into_result()
+}
+
+async fn signup() -> Result<HttpResponse, SignupError> {
+    validate_signup("ada", "short", "123").map_err(SignupError::Invalid)?;
+    Ok(HttpResponse::Ok().finish())
+}
+
+#[actix_web::test]
+async fn test_validation_errors_builder() {
+    assert!(validate_signup("ada@example.com", "password1", "69100").is_ok());
+
+    let errors = validate_signup("ada", "short", "123").unwrap_err();
+    assert_eq!(errors.len(), 4);
+    assert_eq!(errors.field("password").len(), 2);
+    assert_eq!(errors.field("address.zip")[0].code, "invalid_zip");
+    assert!(errors.field("unknown").is_empty());
+
+    let built = ValidationErrors::new().with("name", ValidationIssue::new("required", "Name is required"));
+    assert_eq!(built.fields().keys().collect::<Vec<_>>(), vec!["name"]);
+}
+
+#[actix_web::test]
+async fn test_validation_errors_response() {
+    let app = test::init_service(App::new().route("/signup", web::post().to(signup))).await;
+    let res = test::call_service(&app, test::TestRequest::post().uri("/signup").to_request()).await;
+    assert_eq!(res.status(), 422);
+
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(
+        body,
+        json!({
+            "kind": "validation_failed",
+            "message": "Validation failed",
+            "details": {
+                "fields": {
+                    "address.zip": [{"code": "invalid_zip", "message": "Invalid zip code", "params": {}}],
+                    "email": [{"code": "invalid_email", "message": "Email is invalid", "params": {}}],
+                    "password": [
+                        {"code": "too_short", "message": "Password is too short", "params": {"min": 8}},
+                        {"code": "missing_digit", "message": "Password needs a digit", "params": {}}
+                    ]
+                }
+            }
+        })
+    );
+
+    let api_error: ApiError = ValidationErrors::new().with("a", ValidationIssue::new("b", "c")).into();
+    assert_eq!(api_error.code, 422);
+}
@@ -0,0 +1,65 @@
+#![cfg(all(feature = "validator", feature = "actix"))]
+
+use actix_error::*;
+use actix_web::{test, web, App, HttpResponse};
+use serde::Deserialize;
+use serde_json::json;
+use validator::Validate;
+
+#[derive(Debug, Deserialize, Validate)]
+struct Address {
+    #[validate(length(min = 1, message = "City is required"))]
+    city: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+struct Item {
+    #[validate(range(min = 1))]
+    quantity: u32,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+struct Order {
+    #[validate(email(message = "Email is invalid"))]
+    email: String,
+    #[validate(nested)]
+    address: Address,
+    #[validate(nested)]
+    items: Vec<Item>,
+}
+
+async fn create_order(order: ValidatedJson<Order>) -> HttpResponse {
+    HttpResponse::Created().body(order.email.clone())
+}
+
+#[actix_web::test]
+async fn test_validated_json() {
+    let app = test::init_service(App::new().route("/orders", web::post().to(create_order))).await;
+
+    let valid = json!({"email": "ada@example.com", "address": {"city": "Lyon"}, "items": [{"quantity": 2}]});
+    let res = test::call_service(&app, test::TestRequest::post().uri("/orders").set_json(valid).to_request()).await;
+    assert_eq!(res.status(), 201);
+
+    let invalid = json!({"email": "secret-input", "address": {"city": ""}, "items": [{"quantity": 1}, {"quantity": 0}]});
+    let res = test::call_service(&app, test::TestRequest::post().uri("/orders").set_json(invalid).to_request()).await;
+    assert_eq!(res.status(), 422);
+    let body: serde_json::Value = test::read_body_json(res).await;
+    assert_eq!(body["kind"], "validation_failed");
+    assert_eq!(
+        body["details"]["fields"],
+        json!({
+            "address.city": [{"code": "length", "message": "City is required", "params": {"min": 1}}],
+            "email": [{"code": "email", "message": "Email is invalid", "params": {}}],
+            "items[1].quantity": [{"code": "range", "message": "range", "params": {"min": 1}}]
+        })
+    );
+    assert!(!body.to_string().contains("secret-input"));
+
+    // Malformed payloads are still rejected by the JSON extractor.
+    let res = test::call_service(
+        &app,
+        test::TestRequest::post().uri("/orders").insert_header(("Content-Type", "application/json")).set_payload("{").to_request(),
+    )
+    .await;
+    assert_eq!(res.status(), 400);
+}